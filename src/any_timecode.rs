@@ -0,0 +1,390 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker;
+use core::ops;
+use core::str;
+
+use {format_fields, invalid_fields, parse_fields, AnyFrameRate, FrameRate,
+     Timecode, TimecodeError, TimecodeErrorKind, TimecodeFormat};
+
+/// A timecode with a frame rate chosen at runtime.
+///
+/// This has the same fields as [Timecode](struct.Timecode.html), but keeps
+/// its frame rate as an [AnyFrameRate](enum.AnyFrameRate.html) value, for
+/// when the frame rate is not known until the program runs, for instance
+/// when it is read from file metadata.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let frame_rate = AnyFrameRate::new(30000, 1001, true).unwrap();
+///
+/// let mut tc = AnyTimecode::new(frame_rate, 0, 0, 59, 29).unwrap();
+/// tc += 1;
+/// assert_eq!(tc.to_string(), "00:01:00;02");
+/// assert_eq!(tc.frame_number, 1800);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnyTimecode {
+    /// Frame number. The count of frames after `00:00:00:00`
+    pub frame_number: u32,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub frame: u8,
+    frame_rate: AnyFrameRate,
+}
+
+impl AnyTimecode {
+    /// Returns a timecode with the given frame rate and
+    /// hour/minute/second/frame fields.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let timecode = AnyTimecode::new(AnyFrameRate::Fps24, 10, 0, 0, 0)
+    ///     .unwrap();
+    /// assert_eq!(timecode.frame_number, 864000);
+    /// ```
+    pub fn new(
+        frame_rate: AnyFrameRate,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        frame: u8,
+    ) -> Result<AnyTimecode, TimecodeError> {
+        let result = with_frame_rate!(frame_rate, R => {
+            R::calculate_frame_number(
+                hour as u32,
+                minute as u32,
+                second as u32,
+                frame as u32,
+            )
+        });
+
+        match result {
+            Some(frame_number) => Ok(AnyTimecode {
+                frame_number,
+                hour,
+                minute,
+                second,
+                frame,
+                frame_rate,
+            }),
+            None => Err(invalid_fields(
+                frame_rate.fps(),
+                frame_rate.drop_frame(),
+                hour,
+                minute,
+                second,
+                frame,
+            )),
+        }
+    }
+
+    /// Returns a timecode with the given frame rate and frame number.
+    ///
+    /// Like for [Timecode](struct.Timecode.html), the frame number rolls over
+    /// at 24 hours.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = AnyTimecode::from_frame_number(AnyFrameRate::Fps25, 2159999);
+    /// assert_eq!(tc.to_string(), "23:59:59:24");
+    ///
+    /// let tc = AnyTimecode::from_frame_number(AnyFrameRate::Fps25, 2160000);
+    /// assert_eq!(tc.to_string(), "00:00:00:00");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frame number is negative.
+    pub fn from_frame_number(
+        frame_rate: AnyFrameRate,
+        frame_number: i64,
+    ) -> AnyTimecode {
+        assert!(frame_number >= 0, "Frame number can not be negative.");
+
+        let max_frames = frame_rate.max_frames() as i64;
+        let frame_number = (frame_number % max_frames) as u32;
+
+        let (hour, minute, second, frame) = with_frame_rate!(frame_rate, R => {
+            R::calculate_time_code(frame_number)
+        });
+
+        AnyTimecode {
+            frame_number,
+            hour,
+            minute,
+            second,
+            frame,
+            frame_rate,
+        }
+    }
+
+    /// Parse a timecode string with the given frame rate. The same formats
+    /// are accepted as for [Timecode](struct.Timecode.html).
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = AnyTimecode::parse(AnyFrameRate::Fps2997, "00:00:10;00")
+    ///     .unwrap();
+    /// assert_eq!(tc.frame_number, 300);
+    /// ```
+    pub fn parse(
+        frame_rate: AnyFrameRate,
+        s: &str,
+    ) -> Result<AnyTimecode, TimecodeError> {
+        let fields = parse_fields(s, frame_rate.fps())?;
+
+        if fields.drop_frame && !frame_rate.drop_frame() {
+            return Err(fields.drop_frame_notation());
+        }
+
+        AnyTimecode::new(
+            frame_rate,
+            fields.hour as u8,
+            fields.minute,
+            fields.second,
+            fields.frame,
+        )
+        .map_err(|error| fields.locate(error))
+    }
+
+    /// The frame rate of this timecode.
+    pub fn frame_rate(&self) -> AnyFrameRate {
+        self.frame_rate
+    }
+
+    fn offset(self, frames: i128) -> AnyTimecode {
+        let max_frames = self.frame_rate.max_frames() as i128;
+        let frame_number =
+            (self.frame_number as i128 + frames).rem_euclid(max_frames);
+
+        AnyTimecode::from_frame_number(self.frame_rate, frame_number as i64)
+    }
+}
+
+/// Parse a timecode followed by `@` and a frame rate, in the format accepted
+/// by [AnyFrameRate](enum.AnyFrameRate.html).
+///
+/// ```
+/// use video_timecode::*;
+/// use std::str::FromStr;
+///
+/// let tc = AnyTimecode::from_str("01:00:00;00@29.97").unwrap();
+/// assert_eq!(tc.frame_rate(), AnyFrameRate::Fps2997);
+/// assert_eq!(tc.frame_number, 107892);
+/// ```
+impl str::FromStr for AnyTimecode {
+    type Err = TimecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.find('@') {
+            Some(i) => {
+                let frame_rate = s[i + 1..]
+                    .parse()
+                    .map_err(|error: TimecodeError| error.at(i + 1))?;
+
+                AnyTimecode::parse(frame_rate, &s[..i])
+            }
+            None => Err(TimecodeError::unexpected(s, s.len(), "'@'")),
+        }
+    }
+}
+
+/// Format the timecode the same way as [Timecode](struct.Timecode.html).
+///
+/// With the alternate flag (`{:#}`), the frame rate is appended in the format
+/// accepted when parsing an `AnyTimecode`.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc = AnyTimecode::new(AnyFrameRate::Fps2398, 1, 0, 0, 0).unwrap();
+/// assert_eq!(format!("{}", tc), "01:00:00:00");
+/// assert_eq!(format!("{:#}", tc), "01:00:00:00@23.976");
+/// assert_eq!(format!("{:>13}", tc), "  01:00:00:00");
+/// ```
+impl fmt::Display for AnyTimecode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Padding and the frame rate need the whole string to be formatted
+        // first.
+        if f.width().is_some() || f.alternate() {
+            return fmt::Display::fmt(&TimecodeFormat::new().display(*self), f);
+        }
+
+        format_fields(
+            f,
+            self.hour as u64,
+            self.minute,
+            self.second,
+            self.frame,
+            self.frame_rate.fps(),
+            self.frame_rate.drop_frame(),
+        )
+    }
+}
+
+macro_rules! impl_int_all {
+    ($($t:ty)*) => ($(
+        /// Make a new timecode by adding a number of frames to a timecode.
+        impl ops::Add<$t> for AnyTimecode {
+            type Output = Self;
+
+            fn add(self, other: $t) -> Self {
+                self.offset(other as i128)
+            }
+        }
+
+        /// Add a number of frames to a timecode.
+        impl ops::AddAssign<$t> for AnyTimecode {
+            fn add_assign(&mut self, other: $t) {
+                *self = self.offset(other as i128);
+            }
+        }
+
+        /// Make a new timecode by removing a number of frames from a
+        /// timecode.
+        impl ops::Sub<$t> for AnyTimecode {
+            type Output = Self;
+
+            fn sub(self, other: $t) -> Self {
+                self.offset(-(other as i128))
+            }
+        }
+
+        /// Remove a number of frames from a timecode.
+        impl ops::SubAssign<$t> for AnyTimecode {
+            fn sub_assign(&mut self, other: $t) {
+                *self = self.offset(-(other as i128));
+            }
+        }
+    )*)
+}
+impl_int_all! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+
+/// Make a new timecode by adding two timecodes together, like for
+/// [Timecode](struct.Timecode.html).
+///
+/// # Panics
+///
+/// Since the frame rates are not checked at compile time, this panics if
+/// the timecodes have different frame rates.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc1 = AnyTimecode::new(AnyFrameRate::Fps25, 0, 0, 20, 0).unwrap();
+/// let tc2 = AnyTimecode::new(AnyFrameRate::Fps25, 0, 0, 10, 0).unwrap();
+/// assert_eq!((tc1 + tc2).to_string(), "00:00:30:00");
+/// ```
+impl ops::Add for AnyTimecode {
+    type Output = AnyTimecode;
+
+    fn add(self, other: Self) -> Self {
+        assert_eq!(
+            self.frame_rate, other.frame_rate,
+            "Can not add timecodes with different frame rates."
+        );
+
+        self + other.frame_number
+    }
+}
+
+/// Add one timecode to another, of the same frame rate.
+///
+/// # Panics
+///
+/// Panics if the timecodes have different frame rates.
+impl ops::AddAssign for AnyTimecode {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Convert a timecode with a frame rate known at compile time into one with
+/// the frame rate stored at runtime.
+///
+/// If the frame rate has no [AnyFrameRate](enum.AnyFrameRate.html) value,
+/// the `TimecodeErrorKind` field of the error will be
+/// [FrameRateMismatch](enum.TimecodeErrorKind.html#variant.FrameRateMismatch).
+///
+/// ```
+/// use video_timecode::*;
+/// use std::convert::TryFrom;
+///
+/// let tc = Timecode::<FrameRate5994>::new(0, 10, 0, 0).unwrap();
+/// let any = AnyTimecode::try_from(tc).unwrap();
+/// assert_eq!(any.frame_rate(), AnyFrameRate::Fps5994);
+/// assert_eq!(any.frame_number, 35964);
+/// ```
+impl<T> TryFrom<Timecode<T>> for AnyTimecode
+where
+    T: FrameRate,
+{
+    type Error = TimecodeError;
+
+    fn try_from(timecode: Timecode<T>) -> Result<Self, Self::Error> {
+        let frame_rate = T::ANY_FRAME_RATE.ok_or_else(|| {
+            TimecodeError::from(TimecodeErrorKind::FrameRateMismatch)
+        })?;
+
+        Ok(AnyTimecode {
+            frame_number: timecode.frame_number,
+            hour: timecode.hour,
+            minute: timecode.minute,
+            second: timecode.second,
+            frame: timecode.frame,
+            frame_rate,
+        })
+    }
+}
+
+/// Convert a timecode with a runtime frame rate into a timecode with a
+/// frame rate known at compile time.
+///
+/// If the frame rates are different, the `TimecodeErrorKind` field of the
+/// error will be
+/// [FrameRateMismatch](enum.TimecodeErrorKind.html#variant.FrameRateMismatch).
+///
+/// ```
+/// use video_timecode::*;
+/// use video_timecode::TimecodeErrorKind::*;
+/// use std::convert::TryFrom;
+///
+/// let any = AnyTimecode::new(AnyFrameRate::Fps2398, 0, 0, 1, 0).unwrap();
+///
+/// let tc = Timecode::<FrameRate2398>::try_from(any).unwrap();
+/// assert_eq!(tc.frame_number, 24);
+///
+/// match Timecode::<FrameRate24>::try_from(any) {
+///     Err(TimecodeError { kind: FrameRateMismatch, .. }) => {}
+///     _ => panic!()
+/// }
+/// ```
+impl<T> TryFrom<AnyTimecode> for Timecode<T>
+where
+    T: FrameRate,
+{
+    type Error = TimecodeError;
+
+    fn try_from(timecode: AnyTimecode) -> Result<Self, Self::Error> {
+        if Some(timecode.frame_rate) != T::ANY_FRAME_RATE {
+            return Err(TimecodeError::from(
+                TimecodeErrorKind::FrameRateMismatch,
+            ));
+        }
+
+        Ok(Timecode {
+            frame_number: timecode.frame_number,
+            hour: timecode.hour,
+            minute: timecode.minute,
+            second: timecode.second,
+            frame: timecode.frame,
+            frame_rate: marker::PhantomData,
+        })
+    }
+}
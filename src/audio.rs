@@ -0,0 +1,90 @@
+use {FrameRate, Rounding, Timecode};
+
+impl<T> Timecode<T>
+where
+    T: FrameRate,
+{
+    /// The audio sample at the start of this frame, counting from sample
+    /// zero at `00:00:00:00`, at the given sample rate in Hz.
+    ///
+    /// For fractional frame rates a frame does not last a whole number of
+    /// samples, so each frame starts at the sample nearest to its exact
+    /// start time. At 48 kHz and 29.97 fps this gives the five frame cadence
+    /// of 1602, 1601, 1602, 1601 and 1602 samples.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate2997>::new(0, 0, 0, 0).unwrap();
+    /// let starts: Vec<u64> =
+    ///     tc.frames().take(6).map(|tc| tc.to_sample(48000)).collect();
+    /// assert_eq!(starts, [0, 1602, 3203, 4805, 6406, 8008]);
+    ///
+    /// let tc = Timecode::<FrameRate25>::new(1, 0, 0, 0).unwrap();
+    /// assert_eq!(tc.to_sample(96000), 345_600_000);
+    /// ```
+    pub fn to_sample(&self, sample_rate: u32) -> u64 {
+        sample_of_frame::<T>(self.frame_number as i128, sample_rate) as u64
+    }
+
+    /// The number of audio samples in this frame, at the given sample rate
+    /// in Hz.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate2997>::new(0, 0, 0, 1).unwrap();
+    /// assert_eq!(tc.samples_in_frame(48000), 1601);
+    /// ```
+    pub fn samples_in_frame(&self, sample_rate: u32) -> u32 {
+        let frame_number = self.frame_number as i128;
+
+        (sample_of_frame::<T>(frame_number + 1, sample_rate)
+            - sample_of_frame::<T>(frame_number, sample_rate)) as u32
+    }
+
+    /// Returns the timecode of the frame that an audio sample is in, at the
+    /// given sample rate in Hz, and the number of samples from the start of
+    /// that frame to the sample.
+    ///
+    /// Like when creating a timecode from a frame number, the timecode rolls
+    /// over at 24 hours.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let (tc, offset) = Timecode::<FrameRate2997>::from_sample(4000, 48000);
+    /// assert_eq!(tc.to_string(), "00:00:00;02");
+    /// assert_eq!(offset, 797);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` is zero.
+    pub fn from_sample(sample: u64, sample_rate: u32) -> (Self, u32) {
+        assert!(sample_rate > 0, "Sample rate must be positive.");
+
+        // The last frame that starts at or before the sample.
+        let frame_number = Rounding::Ceil.divide(
+            (2 * sample as i128 + 1) * T::RATE_NUMERATOR as i128,
+            2 * sample_rate as i128 * T::RATE_DENOMINATOR as i128,
+        ) - 1;
+
+        let offset =
+            sample as i128 - sample_of_frame::<T>(frame_number, sample_rate);
+        let frame_number = frame_number.rem_euclid(T::MAX_FRAMES as i128);
+
+        (Timecode::from(frame_number as u32), offset as u32)
+    }
+}
+
+/// The sample nearest to the exact start time of a frame.
+fn sample_of_frame<T>(frame_number: i128, sample_rate: u32) -> i128
+where
+    T: FrameRate,
+{
+    Rounding::Nearest.divide(
+        frame_number * sample_rate as i128 * T::RATE_DENOMINATOR as i128,
+        T::RATE_NUMERATOR as i128,
+    )
+}
@@ -0,0 +1,153 @@
+use {FrameRate, FrameRate11988, FrameRate11988Ndf, FrameRate2997,
+     FrameRate2997Ndf, FrameRate5994, FrameRate5994Ndf, Rounding, Timecode,
+     TimecodeError};
+
+/// How to convert a timecode from one frame rate to another, using
+/// [Timecode::convert](struct.Timecode.html#method.convert).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Conversion {
+    /// Keep the real time elapsed since `00:00:00:00`, using the exact frame
+    /// rates, rounding to a frame of the new frame rate as specified.
+    ///
+    /// The timecode rolls over at 24 hours, which matters when converting
+    /// between rates such as 24 and 23.976 fps, where 24 hours of timecode
+    /// is not the same length of real time.
+    RealTime(Rounding),
+
+    /// Keep the hour, minute and second fields, and scale the frame field to
+    /// the nominal frame rate of the new frame rate, rounding down. For
+    /// instance `10:00:00:12` at 25 fps becomes `10:00:00:24` at 50 fps,
+    /// and `10:00:00;15` at 29.97 fps becomes `10:00:00;30` at 59.94 fps.
+    ///
+    /// The policy decides what to do if the new timecode is one that is
+    /// skipped in a drop frame frame rate.
+    Label(DroppedFramePolicy),
+}
+
+/// What to do when converting a timecode label to a drop frame frame rate
+/// gives a timecode that does not exist in that frame rate, like
+/// `00:01:00;00` at 29.97 fps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DroppedFramePolicy {
+    /// Fail with the
+    /// [DroppedFrame](enum.TimecodeErrorReason.html#variant.DroppedFrame)
+    /// error from [Timecode::new](struct.Timecode.html#method.new).
+    Reject,
+
+    /// Use the first timecode after the dropped ones, like `00:01:00;02` at
+    /// 29.97 fps.
+    Next,
+
+    /// Use the last timecode before the dropped ones, like `00:00:59;29` at
+    /// 29.97 fps.
+    Previous,
+}
+
+impl<T> Timecode<T>
+where
+    T: FrameRate,
+{
+    /// Convert a timecode to another frame rate.
+    ///
+    /// Keeping the real time:
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap();
+    /// let converted = tc
+    ///     .convert::<FrameRate25>(Conversion::RealTime(Rounding::Floor))
+    ///     .unwrap();
+    /// assert_eq!(converted.to_string(), "00:59:59:24");
+    /// ```
+    ///
+    /// Keeping the timecode label:
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate30>::new(0, 1, 0, 1).unwrap();
+    /// let policy = DroppedFramePolicy::Next;
+    /// let converted = tc
+    ///     .convert::<FrameRate2997>(Conversion::Label(policy))
+    ///     .unwrap();
+    /// assert_eq!(converted.to_string(), "00:01:00;02");
+    /// ```
+    ///
+    /// Only label conversion with
+    /// [DroppedFramePolicy::Reject](enum.DroppedFramePolicy.html#variant.Reject)
+    /// can fail.
+    pub fn convert<U>(
+        self,
+        conversion: Conversion,
+    ) -> Result<Timecode<U>, TimecodeError>
+    where
+        U: FrameRate,
+    {
+        match conversion {
+            Conversion::RealTime(rounding) => {
+                let frame_number = rounding.divide(
+                    self.frame_number as i128
+                        * T::RATE_DENOMINATOR as i128
+                        * U::RATE_NUMERATOR as i128,
+                    T::RATE_NUMERATOR as i128 * U::RATE_DENOMINATOR as i128,
+                );
+
+                Ok(Timecode::from(
+                    frame_number.rem_euclid(U::MAX_FRAMES as i128) as u32,
+                ))
+            }
+            Conversion::Label(policy) => {
+                let frame = (self.frame as u32 * U::FPS / T::FPS) as u8;
+
+                let error = match Timecode::new(
+                    self.hour,
+                    self.minute,
+                    self.second,
+                    frame,
+                ) {
+                    Ok(timecode) => return Ok(timecode),
+                    Err(error) => error,
+                };
+
+                let first_after_drop = Timecode::<U>::new(
+                    self.hour,
+                    self.minute,
+                    self.second,
+                    U::DROP_FRAME_COUNT as u8,
+                )?;
+
+                match policy {
+                    DroppedFramePolicy::Reject => Err(error),
+                    DroppedFramePolicy::Next => Ok(first_after_drop),
+                    DroppedFramePolicy::Previous => Ok(first_after_drop - 1),
+                }
+            }
+        }
+    }
+}
+
+macro_rules! impl_drop_frame_relabel {
+    ($($from:ident => $to:ident),*) => ($(
+        /// Relabel a frame between drop frame and non drop frame timecode.
+        /// The frame number, and so the real time, stays the same.
+        ///
+        /// Since 24 hours of drop frame timecode has fewer frames than 24
+        /// hours of non drop frame timecode, non drop frame timecodes after
+        /// the last drop frame timecode of the day roll over to the start of
+        /// the day.
+        impl From<Timecode<$from>> for Timecode<$to> {
+            fn from(timecode: Timecode<$from>) -> Self {
+                Timecode::from(timecode.frame_number)
+            }
+        }
+    )*)
+}
+impl_drop_frame_relabel! {
+    FrameRate2997 => FrameRate2997Ndf,
+    FrameRate2997Ndf => FrameRate2997,
+    FrameRate5994 => FrameRate5994Ndf,
+    FrameRate5994Ndf => FrameRate5994,
+    FrameRate11988 => FrameRate11988Ndf,
+    FrameRate11988Ndf => FrameRate11988
+}
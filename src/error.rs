@@ -0,0 +1,283 @@
+#[cfg(feature = "std")]
+use std::error;
+use core::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// All [Result](https://doc.rust-lang.org/std/result/enum.Result.html) values
+/// returned by this library will use this for error values.
+///
+/// Besides the general [kind](enum.TimecodeErrorKind.html) of error, it can
+/// say what exactly was wrong, and where in the input, so that it can be
+/// shown to the person who wrote the timecode.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let error = "00:00:10:25".parse::<Timecode<FrameRate25>>().unwrap_err();
+/// assert_eq!(error.kind(), TimecodeErrorKind::InvalidTimecode);
+/// assert_eq!(
+///     error.reason(),
+///     Some(&TimecodeErrorReason::OutOfRange {
+///         field: TimecodeField::Frame,
+///         value: 25,
+///         min: 0,
+///         max: 24,
+///     })
+/// );
+/// assert_eq!(error.position(), Some(9));
+/// assert_eq!(
+///     error.to_string(),
+///     "invalid timecode: frame 25 is not between 0 and 24, at position 9"
+/// );
+/// ```
+///
+/// More fields may be added, so patterns matching the struct need a `..`.
+#[non_exhaustive]
+pub struct TimecodeError {
+    pub kind: TimecodeErrorKind,
+
+    /// What exactly was wrong, if known.
+    pub reason: Option<TimecodeErrorReason>,
+
+    /// For errors from parsing, the position in the input of the character
+    /// or field that was wrong, counting from zero.
+    pub position: Option<usize>,
+}
+
+impl TimecodeError {
+    /// The general kind of error.
+    pub fn kind(&self) -> TimecodeErrorKind {
+        self.kind
+    }
+
+    /// What exactly was wrong, if known.
+    pub fn reason(&self) -> Option<&TimecodeErrorReason> {
+        self.reason.as_ref()
+    }
+
+    /// For errors from parsing, the position in the input of the character
+    /// or field that was wrong, counting from zero.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+
+    fn new(kind: TimecodeErrorKind, reason: TimecodeErrorReason) -> Self {
+        TimecodeError {
+            kind,
+            reason: Some(reason),
+            position: None,
+        }
+    }
+
+    /// An error for a field with a value outside of its range.
+    pub(crate) fn out_of_range(
+        kind: TimecodeErrorKind,
+        field: TimecodeField,
+        value: u64,
+        max: u64,
+    ) -> Self {
+        TimecodeError::new(
+            kind,
+            TimecodeErrorReason::OutOfRange {
+                field,
+                value,
+                min: 0,
+                max,
+            },
+        )
+    }
+
+    /// An error for a dropped frame in drop frame timecode.
+    pub(crate) fn dropped_frame(minute: u8, frame: u8) -> Self {
+        TimecodeError::new(
+            TimecodeErrorKind::InvalidTimecode,
+            TimecodeErrorReason::DroppedFrame { minute, frame },
+        )
+    }
+
+    /// An error for the character at `position` in `s`, which should have
+    /// been what is described by `expected`.
+    pub(crate) fn unexpected(
+        s: &str,
+        position: usize,
+        expected: &'static str,
+    ) -> Self {
+        let reason = match s.get(position..).and_then(|s| s.chars().next()) {
+            Some(found) => {
+                TimecodeErrorReason::UnexpectedCharacter { found, expected }
+            }
+            None => TimecodeErrorReason::UnexpectedEnd { expected },
+        };
+
+        TimecodeError::new(TimecodeErrorKind::InvalidFormat, reason)
+            .at(position)
+    }
+
+    /// An error for drop frame notation with a non drop frame rate.
+    pub(crate) fn drop_frame_notation() -> Self {
+        TimecodeError::new(
+            TimecodeErrorKind::InvalidFormat,
+            TimecodeErrorReason::DropFrameNotation,
+        )
+    }
+
+    /// Set the position of the error in the input, unless it is already
+    /// known.
+    pub(crate) fn at(self, position: usize) -> Self {
+        TimecodeError {
+            position: self.position.or(Some(position)),
+            ..self
+        }
+    }
+
+    /// Move the position of the error, for input that was parsed after
+    /// skipping `offset` bytes.
+    pub(crate) fn offset(self, offset: usize) -> Self {
+        TimecodeError {
+            position: self.position.map(|position| position + offset),
+            ..self
+        }
+    }
+}
+
+impl From<TimecodeErrorKind> for TimecodeError {
+    fn from(kind: TimecodeErrorKind) -> Self {
+        TimecodeError {
+            kind,
+            reason: None,
+            position: None,
+        }
+    }
+}
+
+/// Describe the error, like
+/// `invalid timecode: frame 25 is not between 0 and 24, at position 9`.
+impl fmt::Display for TimecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if let Some(ref reason) = self.reason {
+            write!(f, ": {}", reason)?;
+        }
+
+        if let Some(position) = self.position {
+            write!(f, ", at position {}", position)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for TimecodeError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Error values for this library.
+pub enum TimecodeErrorKind {
+    /// Timecode parsing failed due to input having an invalid format.
+    InvalidFormat,
+
+    /// Timecode had an invalid value. For instance the frame field might have
+    /// a value higher than the frame rate allows.
+    InvalidTimecode,
+
+    /// Conversion between timecodes failed because they have different frame
+    /// rates.
+    FrameRateMismatch,
+}
+
+impl fmt::Display for TimecodeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            TimecodeErrorKind::InvalidFormat => "invalid timecode format",
+            TimecodeErrorKind::InvalidTimecode => "invalid timecode",
+            TimecodeErrorKind::FrameRateMismatch => "frame rates do not match",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// What exactly was wrong, for a [TimecodeError](struct.TimecodeError.html).
+pub enum TimecodeErrorReason {
+    /// A field had a value outside of the range allowed for it, from `min`
+    /// to `max` inclusive.
+    OutOfRange {
+        field: TimecodeField,
+        value: u64,
+        min: u64,
+        max: u64,
+    },
+
+    /// The timecode is one of the frames that drop frame timecode skips, at
+    /// the start of each minute except every tenth minute.
+    DroppedFrame { minute: u8, frame: u8 },
+
+    /// A character was not allowed where it was. `expected` describes what
+    /// was allowed, like `"a digit"`.
+    UnexpectedCharacter { found: char, expected: &'static str },
+
+    /// The input ended before the timecode did.
+    UnexpectedEnd { expected: &'static str },
+
+    /// Drop frame notation, with a `;` or `.` before the frame field, was
+    /// used for a non drop frame rate.
+    DropFrameNotation,
+}
+
+impl fmt::Display for TimecodeErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::TimecodeErrorReason::*;
+
+        match *self {
+            OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "{} {} is not between {} and {}",
+                field, value, min, max
+            ),
+            DroppedFrame { minute, frame } => write!(
+                f,
+                "frame {} of minute {} is dropped in drop frame timecode",
+                frame, minute
+            ),
+            UnexpectedCharacter { found, expected } => {
+                write!(f, "expected {}, found {:?}", expected, found)
+            }
+            UnexpectedEnd { expected } => {
+                write!(f, "expected {}, found the end", expected)
+            }
+            DropFrameNotation => {
+                f.write_str("drop frame notation for a non drop frame rate")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A field of a timecode, for errors about it.
+pub enum TimecodeField {
+    Hour,
+    Minute,
+    Second,
+    Frame,
+    Subframe,
+    /// A frame number counted from `00:00:00:00`.
+    FrameNumber,
+}
+
+impl fmt::Display for TimecodeField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            TimecodeField::Hour => "hour",
+            TimecodeField::Minute => "minute",
+            TimecodeField::Second => "second",
+            TimecodeField::Frame => "frame",
+            TimecodeField::Subframe => "subframe",
+            TimecodeField::FrameNumber => "frame number",
+        })
+    }
+}
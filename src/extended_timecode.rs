@@ -0,0 +1,472 @@
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker;
+use core::ops;
+use core::str;
+
+use {format_fields, parse_long_fields, FrameRate, Timecode, TimecodeDuration,
+     TimecodeError, TimecodeErrorKind, TimecodeField};
+
+/// A timecode that does not roll over at 24 hours, generic over types
+/// implementing the trait [FrameRate](trait.FrameRate.html).
+///
+/// The hour field counts on past 23, so this can label long recordings and
+/// logs that run for days. The frame number counts every frame since
+/// `00:00:00:00` of the first day, and each day has the same frames as a
+/// [Timecode](struct.Timecode.html).
+///
+/// ```
+/// use video_timecode::*;
+/// use std::str::FromStr;
+///
+/// let tc = ExtendedTimecode::<FrameRate25>::from_str("100:00:00:00").unwrap();
+/// assert_eq!(tc.frame_number, 9000000);
+/// assert_eq!(tc.days(), 4);
+/// assert_eq!(format!("{:#}", tc), "4d 04:00:00:00");
+///
+/// let tc = tc + 25u32;
+/// assert_eq!(tc.to_string(), "100:00:01:00");
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct ExtendedTimecode<FrameRate> {
+    /// Frame number. The count of frames after `00:00:00:00`
+    pub frame_number: u64,
+    pub hour: u64,
+    pub minute: u8,
+    pub second: u8,
+    pub frame: u8,
+    frame_rate: marker::PhantomData<FrameRate>,
+}
+
+impl<T> Clone for ExtendedTimecode<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ExtendedTimecode<T> {}
+
+impl<T> ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    /// Returns a timecode with the given hour/minute/second/frame fields.
+    /// The hour may be 24 or more.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = ExtendedTimecode::<FrameRate2997>::new(24, 0, 0, 0).unwrap();
+    /// assert_eq!(tc.frame_number, 2589408);
+    /// ```
+    pub fn new(
+        hour: u64,
+        minute: u8,
+        second: u8,
+        frame: u8,
+    ) -> Result<ExtendedTimecode<T>, TimecodeError> {
+        let timecode =
+            Timecode::<T>::new((hour % 24) as u8, minute, second, frame)?;
+
+        let frame_number = (hour / 24)
+            .checked_mul(T::MAX_FRAMES as u64)
+            .and_then(|n| n.checked_add(timecode.frame_number as u64));
+
+        match frame_number {
+            Some(frame_number) => Ok(ExtendedTimecode {
+                frame_number,
+                hour,
+                minute,
+                second,
+                frame,
+                frame_rate: marker::PhantomData,
+            }),
+            None => Err(TimecodeError::from(
+                TimecodeErrorKind::InvalidTimecode,
+            )),
+        }
+    }
+
+    /// The number of whole days since `00:00:00:00` of the first day.
+    pub fn days(&self) -> u64 {
+        self.hour / 24
+    }
+
+    /// The timecode within the day, rolling over at 24 hours.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = ExtendedTimecode::<FrameRate24>::new(49, 0, 0, 0).unwrap();
+    /// assert_eq!(tc.time_of_day().to_string(), "01:00:00:00");
+    /// ```
+    pub fn time_of_day(&self) -> Timecode<T> {
+        Timecode::from(self.frame_number % T::MAX_FRAMES as u64)
+    }
+
+    /// Add a number of frames to a timecode, returning `None` if the result
+    /// is before `00:00:00:00` or too large.
+    ///
+    /// To add a [TimecodeDuration](struct.TimecodeDuration.html), pass its
+    /// number of frames.
+    pub fn checked_add(self, frames: i64) -> Option<Self> {
+        let frame_number = self.frame_number as i128 + frames as i128;
+
+        u64::try_from(frame_number).ok().map(ExtendedTimecode::from)
+    }
+
+    /// Remove a number of frames from a timecode, returning `None` if the
+    /// result is before `00:00:00:00` or too large.
+    pub fn checked_sub(self, frames: i64) -> Option<Self> {
+        self.checked_add(frames.checked_neg()?)
+    }
+
+    /// Add a number of frames to a timecode, stopping at `00:00:00:00` or
+    /// the largest possible timecode.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = ExtendedTimecode::<FrameRate25>::new(0, 0, 0, 10).unwrap();
+    /// assert_eq!(tc.saturating_add(-20).to_string(), "00:00:00:00");
+    /// ```
+    pub fn saturating_add(self, frames: i64) -> Self {
+        let frame_number = (self.frame_number as i128 + frames as i128)
+            .clamp(0, u64::MAX as i128);
+
+        ExtendedTimecode::from(frame_number as u64)
+    }
+
+    /// Remove a number of frames from a timecode, stopping at `00:00:00:00`
+    /// or the largest possible timecode.
+    pub fn saturating_sub(self, frames: i64) -> Self {
+        self.saturating_add(frames.saturating_neg())
+    }
+
+    /// Move the timecode by a number of frames, panicking if the result is
+    /// out of range, like integer arithmetic does.
+    fn offset(self, frames: i128) -> Self {
+        let frame_number = self.frame_number as i128 + frames;
+
+        match u64::try_from(frame_number) {
+            Ok(frame_number) => ExtendedTimecode::from(frame_number),
+            Err(_) => panic!("Timecode out of range: {:?}", frame_number),
+        }
+    }
+}
+
+/// Parse a string into a timecode.
+///
+/// This takes the same formats as a [Timecode](struct.Timecode.html), with
+/// an hour field of two or more digits, like `100:00:00:00`. The timecode
+/// may also start with a day count, in which case the hour field is within
+/// the day, like `1d 02:00:00:00`.
+///
+/// ```
+/// use video_timecode::*;
+/// use std::str::FromStr;
+///
+/// let tc1 = ExtendedTimecode::<FrameRate24>::from_str("26:00:00:00").unwrap();
+/// let tc2 = ExtendedTimecode::<FrameRate24>::from_str("1d 02:00:00:00")
+///     .unwrap();
+/// assert_eq!(tc1, tc2);
+///
+/// assert!(ExtendedTimecode::<FrameRate24>::from_str("1d 26:00:00:00")
+///     .is_err());
+/// ```
+impl<T> str::FromStr for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    type Err = TimecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::TimecodeErrorKind::*;
+
+        let (days, rest) = match s.split_once("d ") {
+            Some((days, rest)) => {
+                let digits =
+                    days.bytes().take_while(u8::is_ascii_digit).count();
+
+                if digits == 0 || digits < days.len() {
+                    return Err(TimecodeError::unexpected(s, digits, "a digit"));
+                }
+
+                match days.parse::<u64>() {
+                    Ok(days) => (Some(days), rest),
+                    Err(_) => {
+                        return Err(TimecodeError::from(InvalidFormat).at(0));
+                    }
+                }
+            }
+            None => (None, s),
+        };
+
+        // The position of the timecode after the days.
+        let offset = s.len() - rest.len();
+        let max_hour_digits = if days.is_some() { 2 } else { usize::MAX };
+
+        let fields = parse_long_fields(rest, T::FPS, max_hour_digits)
+            .map_err(|error| error.offset(offset))?;
+
+        if fields.drop_frame && !T::DROP_FRAME {
+            return Err(fields.drop_frame_notation().offset(offset));
+        }
+
+        let hour = match days {
+            Some(_) if fields.hour > 23 => {
+                return Err(TimecodeError::out_of_range(
+                    InvalidTimecode,
+                    TimecodeField::Hour,
+                    fields.hour,
+                    23,
+                )
+                .at(offset));
+            }
+            Some(days) => days
+                .checked_mul(24)
+                .and_then(|hours| hours.checked_add(fields.hour)),
+            None => Some(fields.hour),
+        };
+
+        match hour {
+            Some(hour) => ExtendedTimecode::new(
+                hour,
+                fields.minute,
+                fields.second,
+                fields.frame,
+            )
+            .map_err(|error| fields.locate(error).offset(offset)),
+            None => Err(TimecodeError::from(InvalidTimecode)),
+        }
+    }
+}
+
+/// Format the timecode as `HH:MM:SS:FF`, with as many hour digits as needed.
+///
+/// The alternate form, `{:#}`, puts the number of days in front of the
+/// timecode within the day, like `1d 02:00:00:00`, if the timecode is past
+/// the first day.
+impl<T> fmt::Display for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hour = if f.alternate() && self.days() > 0 {
+            write!(f, "{}d ", self.days())?;
+            self.hour % 24
+        } else {
+            self.hour
+        };
+
+        format_fields(
+            f,
+            hour,
+            self.minute,
+            self.second,
+            self.frame,
+            T::FPS,
+            T::DROP_FRAME,
+        )
+    }
+}
+
+macro_rules! impl_int_all {
+    ($($t:ty)*) => ($(
+        /// Make a new timecode by adding a number of frames to a timecode.
+        ///
+        /// Panics if the result is before `00:00:00:00` or too large.
+        impl<T> ops::Add<$t> for ExtendedTimecode<T>
+        where
+            T: FrameRate,
+        {
+            type Output = Self;
+
+            fn add(self, other: $t) -> Self {
+                self.offset(other as i128)
+            }
+        }
+
+        /// Add a number of frames to a timecode.
+        impl<T> ops::AddAssign<$t> for ExtendedTimecode<T>
+        where
+            T: FrameRate,
+        {
+            fn add_assign(&mut self, other: $t) {
+                *self = self.offset(other as i128);
+            }
+        }
+
+        /// Make a new timecode by removing a number of frames from a
+        /// timecode.
+        ///
+        /// Panics if the result is before `00:00:00:00` or too large.
+        impl<T> ops::Sub<$t> for ExtendedTimecode<T>
+        where
+            T: FrameRate,
+        {
+            type Output = Self;
+
+            fn sub(self, other: $t) -> Self {
+                self.offset(-(other as i128))
+            }
+        }
+
+        /// Remove a number of frames from a timecode.
+        impl<T> ops::SubAssign<$t> for ExtendedTimecode<T>
+        where
+            T: FrameRate,
+        {
+            fn sub_assign(&mut self, other: $t) {
+                *self = self.offset(-(other as i128));
+            }
+        }
+    )*)
+}
+impl_int_all! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty)*) => ($(
+        /// Create a timecode with the the given frame number.
+        impl<T> From<$t> for ExtendedTimecode<T>
+        where
+            T: FrameRate,
+        {
+            fn from(frame_number: $t) -> Self {
+                let frame_number = frame_number as u64;
+                let days = frame_number / T::MAX_FRAMES as u64;
+                let (hour, minute, second, frame) = T::calculate_time_code(
+                    (frame_number % T::MAX_FRAMES as u64) as u32,
+                );
+
+                ExtendedTimecode {
+                    frame_number,
+                    hour: days * 24 + hour as u64,
+                    minute,
+                    second,
+                    frame,
+                    frame_rate: marker::PhantomData,
+                }
+            }
+        }
+    )*)
+}
+impl_from_unsigned! { usize u8 u16 u32 u64 }
+
+/// Make a new timecode by adding two timecodes together. The result is a
+/// timecode where the field `frame_number` is the sum of the frame numbers
+/// of the two added timecodes.
+impl<T> ops::Add for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    type Output = ExtendedTimecode<T>;
+
+    fn add(self, other: Self) -> Self {
+        self + other.frame_number
+    }
+}
+
+impl<T> ops::AddAssign for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    fn add_assign(&mut self, other: Self) {
+        *self += other.frame_number;
+    }
+}
+
+/// The duration between two timecodes. This is negative if the first
+/// timecode is earlier than the second.
+impl<T> ops::Sub for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    type Output = TimecodeDuration<T>;
+
+    fn sub(self, other: Self) -> TimecodeDuration<T> {
+        TimecodeDuration::from_frames(
+            self.frame_number as i64 - other.frame_number as i64,
+        )
+    }
+}
+
+/// Move a timecode forward by a duration, or backward if the duration is
+/// negative.
+impl<T> ops::Add<TimecodeDuration<T>> for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    type Output = ExtendedTimecode<T>;
+
+    fn add(self, other: TimecodeDuration<T>) -> ExtendedTimecode<T> {
+        self + other.frames()
+    }
+}
+
+impl<T> ops::AddAssign<TimecodeDuration<T>> for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    fn add_assign(&mut self, other: TimecodeDuration<T>) {
+        *self += other.frames();
+    }
+}
+
+/// Move a timecode backward by a duration, or forward if the duration is
+/// negative.
+impl<T> ops::Sub<TimecodeDuration<T>> for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    type Output = ExtendedTimecode<T>;
+
+    fn sub(self, other: TimecodeDuration<T>) -> ExtendedTimecode<T> {
+        self - other.frames()
+    }
+}
+
+impl<T> ops::SubAssign<TimecodeDuration<T>> for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    fn sub_assign(&mut self, other: TimecodeDuration<T>) {
+        *self -= other.frames();
+    }
+}
+
+impl<T> From<Timecode<T>> for ExtendedTimecode<T>
+where
+    T: FrameRate,
+{
+    fn from(timecode: Timecode<T>) -> Self {
+        ExtendedTimecode {
+            frame_number: timecode.frame_number as u64,
+            hour: timecode.hour as u64,
+            minute: timecode.minute,
+            second: timecode.second,
+            frame: timecode.frame,
+            frame_rate: marker::PhantomData,
+        }
+    }
+}
+
+/// Convert an extended timecode to a timecode, failing with an
+/// [InvalidTimecode](enum.TimecodeErrorKind.html#variant.InvalidTimecode)
+/// error if it is not within the first day. Use
+/// [time_of_day](struct.ExtendedTimecode.html#method.time_of_day) to roll
+/// over instead.
+impl<T> TryFrom<ExtendedTimecode<T>> for Timecode<T>
+where
+    T: FrameRate,
+{
+    type Error = TimecodeError;
+
+    fn try_from(timecode: ExtendedTimecode<T>) -> Result<Self, TimecodeError> {
+        if timecode.days() > 0 {
+            return Err(TimecodeError::from(TimecodeErrorKind::InvalidTimecode));
+        }
+
+        Ok(timecode.time_of_day())
+    }
+}
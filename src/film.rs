@@ -0,0 +1,379 @@
+//! Film positions in feet and frames, and keycode edge numbers.
+//!
+//! Film is measured along its length in feet, and the frames within a foot.
+//! How many frames fit in a foot depends on the gauge and on how many
+//! perforations each frame is tall. 35mm film has 64 perforations per foot,
+//! so 4-perf has 16 frames per foot and 2-perf has 32. A 3-perf frame does
+//! not fit a whole number of times into a foot, so the feet have 22, 21 and
+//! 21 frames in turn. 16mm film has one perforation per frame and 40 frames
+//! per foot.
+//!
+//! A frame belongs to the foot that its first perforation is in. Positions
+//! are counted from the frame with frame number zero, so they line up with
+//! the `frame_number` field of a [Timecode](../struct.Timecode.html).
+//!
+//! ```
+//! use video_timecode::*;
+//! use video_timecode::film::*;
+//!
+//! let tc = Timecode::<FrameRate24>::new(0, 1, 0, 0).unwrap();
+//! let position = tc.to_feet_frames(FilmFormat::ThirtyFive4Perf);
+//! assert_eq!(position.to_string(), "90+00");
+//!
+//! let position: FeetFrames = "1234+07".parse().unwrap();
+//! let tc = Timecode::<FrameRate24>::from_feet_frames(
+//!     position,
+//!     FilmFormat::ThirtyFive4Perf,
+//! ).unwrap();
+//! assert_eq!(tc.to_string(), "00:13:42:23");
+//! ```
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops;
+use core::str;
+
+use {FrameRate, Timecode, TimecodeError, TimecodeErrorKind};
+
+/// A film gauge and the number of perforations each frame takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FilmFormat {
+    /// 35mm film with 4 perforations per frame, 16 frames per foot.
+    ThirtyFive4Perf,
+
+    /// 35mm film with 3 perforations per frame, 64 frames every 3 feet.
+    ThirtyFive3Perf,
+
+    /// 35mm film with 2 perforations per frame, 32 frames per foot.
+    ThirtyFive2Perf,
+
+    /// 16mm film with 1 perforation per frame, 40 frames per foot.
+    Sixteen,
+}
+
+impl FilmFormat {
+    /// The number of perforations each frame takes.
+    pub fn perforations_per_frame(self) -> u32 {
+        match self {
+            FilmFormat::ThirtyFive4Perf => 4,
+            FilmFormat::ThirtyFive3Perf => 3,
+            FilmFormat::ThirtyFive2Perf => 2,
+            FilmFormat::Sixteen => 1,
+        }
+    }
+
+    /// The number of perforations in a foot of film.
+    pub fn perforations_per_foot(self) -> u32 {
+        match self {
+            FilmFormat::Sixteen => 40,
+            _ => 64,
+        }
+    }
+
+    /// The number of the first frame in a foot.
+    fn first_frame(self, feet: u64) -> u128 {
+        let perforations = feet as u128 * self.perforations_per_foot() as u128;
+        let per_frame = self.perforations_per_frame() as u128;
+
+        (perforations + per_frame - 1) / per_frame
+    }
+}
+
+/// A film position in feet and frames, written like `1234+07`.
+///
+/// The frames field is the number of frames since the first frame in the
+/// foot, and is only checked against the length of the foot when the
+/// position is converted to a frame number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FeetFrames {
+    pub feet: u64,
+    pub frames: u8,
+}
+
+impl FeetFrames {
+    /// Returns the position of a frame, counting from frame zero.
+    ///
+    /// ```
+    /// use video_timecode::film::*;
+    ///
+    /// let position = |n| {
+    ///     FeetFrames::from_frame_number(n, FilmFormat::ThirtyFive3Perf)
+    ///         .to_string()
+    /// };
+    /// assert_eq!(position(21), "0+21");
+    /// assert_eq!(position(22), "1+00");
+    /// assert_eq!(position(64), "3+00");
+    /// ```
+    pub fn from_frame_number(frame_number: u64, format: FilmFormat) -> Self {
+        let perforation =
+            frame_number as u128 * format.perforations_per_frame() as u128;
+        let feet =
+            (perforation / format.perforations_per_foot() as u128) as u64;
+
+        FeetFrames {
+            feet,
+            frames: (frame_number as u128 - format.first_frame(feet)) as u8,
+        }
+    }
+
+    /// The number of the frame at this position, counting from frame zero.
+    ///
+    /// If the frames field is past the end of the foot, an error with the
+    /// `TimecodeErrorKind`
+    /// [InvalidTimecode](../enum.TimecodeErrorKind.html#variant.InvalidTimecode)
+    /// is returned.
+    pub fn to_frame_number(
+        &self,
+        format: FilmFormat,
+    ) -> Result<u64, TimecodeError> {
+        let frame_number =
+            format.first_frame(self.feet) + self.frames as u128;
+
+        match u64::try_from(frame_number) {
+            Ok(n) if (n as u128) < format.first_frame(self.feet + 1) => Ok(n),
+            _ => Err(TimecodeError::from(TimecodeErrorKind::InvalidTimecode)),
+        }
+    }
+}
+
+/// Parse a position written as feet and frames, like `1234+07`.
+impl str::FromStr for FeetFrames {
+    type Err = TimecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (feet, frames) = s.split_once('+').ok_or_else(invalid_format)?;
+
+        if !is_number(feet, 1..=20) || !is_number(frames, 1..=2) {
+            return Err(invalid_format());
+        }
+
+        match (feet.parse(), frames.parse()) {
+            (Ok(feet), Ok(frames)) => Ok(FeetFrames { feet, frames }),
+            _ => Err(invalid_format()),
+        }
+    }
+}
+
+/// Format the position as `FEET+FF`, like `1234+07`.
+impl fmt::Display for FeetFrames {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}+{:02}", self.feet, self.frames)
+    }
+}
+
+/// Whether a string is only ASCII digits, with a length in the given range.
+fn is_number(s: &str, digits: ops::RangeInclusive<usize>) -> bool {
+    digits.contains(&s.len()) && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn invalid_format() -> TimecodeError {
+    TimecodeError::from(TimecodeErrorKind::InvalidFormat)
+}
+
+impl<T> Timecode<T>
+where
+    T: FrameRate,
+{
+    /// The film position of this timecode, where `00:00:00:00` is `0+00`.
+    pub fn to_feet_frames(&self, format: FilmFormat) -> FeetFrames {
+        FeetFrames::from_frame_number(self.frame_number as u64, format)
+    }
+
+    /// Returns the timecode of a film position, where `0+00` is
+    /// `00:00:00:00`.
+    ///
+    /// If the position is not a valid frame, or is 24 hours or more, an
+    /// error with the `TimecodeErrorKind`
+    /// [InvalidTimecode](../enum.TimecodeErrorKind.html#variant.InvalidTimecode)
+    /// is returned.
+    pub fn from_feet_frames(
+        position: FeetFrames,
+        format: FilmFormat,
+    ) -> Result<Timecode<T>, TimecodeError> {
+        let frame_number = position.to_frame_number(format)?;
+
+        if frame_number >= T::MAX_FRAMES as u64 {
+            return Err(TimecodeError::from(TimecodeErrorKind::InvalidTimecode));
+        }
+
+        Ok(Timecode::from(frame_number))
+    }
+}
+
+/// A keycode, the edge number printed along the side of film stock, like
+/// `KU 22 9012 1234+12`.
+///
+/// The key number counts feet along the roll, and the frames field counts
+/// frames from the key. For 3-perf film, where frames do not line up with
+/// the feet, the perforation offset is the number of perforations from the
+/// frames counted from the key to the start of the frame.
+///
+/// ```
+/// use video_timecode::film::*;
+///
+/// let keycode: KeyCode = "KU 22 9012 1234+12".parse().unwrap();
+/// assert_eq!(keycode.feet_frames.feet, 1234);
+///
+/// let later = keycode.offset(10, FilmFormat::ThirtyFive4Perf).unwrap();
+/// assert_eq!(later.to_string(), "KU 22 9012 1235+06");
+/// assert_eq!(
+///     later.frames_since(&keycode, FilmFormat::ThirtyFive4Perf),
+///     Some(10)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCode {
+    /// Manufacturer code, like `K` for Kodak.
+    pub manufacturer: char,
+
+    /// Film stock code.
+    pub film_type: char,
+
+    /// The six digit prefix identifying the roll.
+    pub prefix: u32,
+
+    /// The key number, in feet, and the frames after it.
+    pub feet_frames: FeetFrames,
+
+    /// The number of perforations from the key to the start of the frame.
+    pub perforation_offset: u8,
+}
+
+impl KeyCode {
+    /// The keycode a number of frames later on the same roll, or earlier if
+    /// `frames` is negative. Returns `None` if the key number would be
+    /// negative or longer than four digits.
+    pub fn offset(&self, frames: i64, format: FilmFormat) -> Option<KeyCode> {
+        let per_frame = format.perforations_per_frame() as i64;
+        let per_foot = format.perforations_per_foot() as i64;
+
+        let perforation = frames
+            .checked_mul(per_frame)?
+            .checked_add(self.perforation(format))?;
+
+        let feet = perforation.div_euclid(per_foot);
+        let in_foot = perforation.rem_euclid(per_foot);
+
+        if !(0..=9999).contains(&feet) {
+            return None;
+        }
+
+        Some(KeyCode {
+            feet_frames: FeetFrames {
+                feet: feet as u64,
+                frames: (in_foot / per_frame) as u8,
+            },
+            perforation_offset: (in_foot % per_frame) as u8,
+            ..*self
+        })
+    }
+
+    /// The number of frames from another keycode to this one. Returns `None`
+    /// if the keycodes are on different rolls, or the frames do not line up.
+    pub fn frames_since(
+        &self,
+        other: &KeyCode,
+        format: FilmFormat,
+    ) -> Option<i64> {
+        if (self.manufacturer, self.film_type, self.prefix)
+            != (other.manufacturer, other.film_type, other.prefix)
+        {
+            return None;
+        }
+
+        let perforations = self.perforation(format) - other.perforation(format);
+        let per_frame = format.perforations_per_frame() as i64;
+
+        if perforations % per_frame != 0 {
+            return None;
+        }
+
+        Some(perforations / per_frame)
+    }
+
+    /// The perforation at the start of the frame, counted from key zero.
+    fn perforation(&self, format: FilmFormat) -> i64 {
+        self.feet_frames.feet as i64 * format.perforations_per_foot() as i64
+            + self.feet_frames.frames as i64
+                * format.perforations_per_frame() as i64
+            + self.perforation_offset as i64
+    }
+}
+
+/// Parse a keycode written like `KU 22 9012 1234+12`, with an optional
+/// perforation offset at the end like `KU 22 9012 1234+12.2`.
+impl str::FromStr for KeyCode {
+    type Err = TimecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(' ');
+        let (codes, prefix_high, prefix_low, key) =
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(a), Some(b), Some(c), Some(d)) => (a, b, c, d),
+                _ => return Err(invalid_format()),
+            };
+
+        let mut chars = codes.chars();
+        let (manufacturer, film_type) =
+            match (chars.next(), chars.next(), chars.next()) {
+                (Some(m), Some(t), None)
+                    if m.is_ascii_alphabetic() && t.is_ascii_alphanumeric() =>
+                {
+                    (m, t)
+                }
+                _ => return Err(invalid_format()),
+            };
+
+        let (key, perforation_offset) = match key.split_once('.') {
+            Some((key, offset)) if is_number(offset, 1..=1) => {
+                (key, offset.as_bytes()[0] - b'0')
+            }
+            Some(_) => return Err(invalid_format()),
+            None => (key, 0),
+        };
+
+        if parts.next().is_some()
+            || !is_number(prefix_high, 2..=2)
+            || !is_number(prefix_low, 4..=4)
+            || key.find('+') != Some(4)
+        {
+            return Err(invalid_format());
+        }
+
+        let prefix_high: u32 =
+            prefix_high.parse().map_err(|_| invalid_format())?;
+        let prefix_low: u32 =
+            prefix_low.parse().map_err(|_| invalid_format())?;
+
+        Ok(KeyCode {
+            manufacturer,
+            film_type,
+            prefix: prefix_high * 10000 + prefix_low,
+            feet_frames: key.parse()?,
+            perforation_offset,
+        })
+    }
+}
+
+/// Format the keycode like `KU 22 9012 1234+12`, adding the perforation
+/// offset like `KU 22 9012 1234+12.2` if it is not zero.
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{} {:02} {:04} {:04}+{:02}",
+            self.manufacturer,
+            self.film_type,
+            self.prefix / 10000,
+            self.prefix % 10000,
+            self.feet_frames.feet,
+            self.feet_frames.frames
+        )?;
+
+        if self.perforation_offset != 0 {
+            write!(f, ".{}", self.perforation_offset)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,407 @@
+use core::fmt;
+use core::str;
+
+use frame_rate::write_rate;
+use {frame_digits, AnyTimecode, FrameRate, Rounding, Timecode};
+
+/// A layout for writing timecodes, for systems that expect something other
+/// than `HH:MM:SS:FF`.
+///
+/// The format is built by chaining methods on
+/// [TimecodeFormat::new](#method.new), which starts out the same as the
+/// `Display` implementation of [Timecode](struct.Timecode.html).
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc = Timecode::<FrameRate2997>::new(0, 1, 2, 3).unwrap();
+///
+/// let format = TimecodeFormat::new().separator(';');
+/// assert_eq!(format.display(tc).to_string(), "00;01;02;03");
+///
+/// let format = TimecodeFormat::new().without_separators();
+/// assert_eq!(format.display(tc).to_string(), "00010203");
+///
+/// let format = TimecodeFormat::new().omit_zero_hours().rate_suffix();
+/// assert_eq!(format.display(tc).to_string(), "01:02;03@29.97DF");
+///
+/// let format = TimecodeFormat::new().frame_count();
+/// assert_eq!(format.display(tc).to_string(), "1861");
+///
+/// let format = TimecodeFormat::new().seconds(3);
+/// assert_eq!(format.display(tc).to_string(), "62.095");
+/// ```
+///
+/// The result can be padded with the usual width, fill and alignment flags,
+/// or written to a byte buffer without allocating.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+/// let format = TimecodeFormat::new().separator('.');
+/// assert_eq!(format!("[{:>13}]", format.display(tc)), "[  10.00.00.00]");
+///
+/// let mut buffer = [0; 16];
+/// assert_eq!(format.write_to(tc, &mut buffer), Ok("10.00.00.00"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimecodeFormat {
+    layout: Layout,
+    separator: Separator,
+    frame_separator: Option<char>,
+    omit_zero_hours: bool,
+    rate_suffix: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Layout {
+    Fields,
+    FrameCount,
+    Seconds(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Separator {
+    /// Colons, and a semicolon before the frame field for drop frame.
+    Standard,
+    Custom(char),
+    Nothing,
+}
+
+impl TimecodeFormat {
+    /// Returns the standard format, `HH:MM:SS:FF`, with a semicolon before
+    /// the frame field for drop frame timecodes.
+    pub fn new() -> TimecodeFormat {
+        TimecodeFormat {
+            layout: Layout::Fields,
+            separator: Separator::Standard,
+            frame_separator: None,
+            omit_zero_hours: false,
+            rate_suffix: false,
+        }
+    }
+
+    /// Use the given separator between all fields, including before the
+    /// frame field, unless that is set with
+    /// [frame_separator](#method.frame_separator).
+    pub fn separator(self, separator: char) -> TimecodeFormat {
+        TimecodeFormat {
+            layout: Layout::Fields,
+            separator: Separator::Custom(separator),
+            ..self
+        }
+    }
+
+    /// Use the given separator before the frame field.
+    pub fn frame_separator(self, separator: char) -> TimecodeFormat {
+        TimecodeFormat {
+            layout: Layout::Fields,
+            frame_separator: Some(separator),
+            ..self
+        }
+    }
+
+    /// Write the fields without separators, like `HHMMSSFF`.
+    pub fn without_separators(self) -> TimecodeFormat {
+        TimecodeFormat {
+            layout: Layout::Fields,
+            separator: Separator::Nothing,
+            frame_separator: None,
+            ..self
+        }
+    }
+
+    /// Leave out the hour field when it is zero, like `MM:SS:FF`.
+    pub fn omit_zero_hours(self) -> TimecodeFormat {
+        TimecodeFormat {
+            layout: Layout::Fields,
+            omit_zero_hours: true,
+            ..self
+        }
+    }
+
+    /// Write the frame number instead of the fields.
+    pub fn frame_count(self) -> TimecodeFormat {
+        TimecodeFormat {
+            layout: Layout::FrameCount,
+            ..self
+        }
+    }
+
+    /// Write the real time since `00:00:00:00` in seconds, using the exact
+    /// frame rate, rounded to the given number of decimals. At most nine
+    /// decimals are written.
+    pub fn seconds(self, decimals: u8) -> TimecodeFormat {
+        TimecodeFormat {
+            layout: Layout::Seconds(decimals.min(9)),
+            ..self
+        }
+    }
+
+    /// Add the frame rate after the timecode, like `10:00:00;00@29.97DF`.
+    pub fn rate_suffix(self) -> TimecodeFormat {
+        TimecodeFormat {
+            rate_suffix: true,
+            ..self
+        }
+    }
+
+    /// A value that writes the timecode in this format when it is
+    /// displayed.
+    pub fn display<A>(self, timecode: A) -> FormattedTimecode
+    where
+        A: Into<FormatFields>,
+    {
+        FormattedTimecode {
+            format: self,
+            timecode: timecode.into(),
+        }
+    }
+
+    /// Write the timecode in this format into a byte buffer, and return the
+    /// written part as a string. If the buffer is too small, an error is
+    /// returned.
+    pub fn write_to<A>(
+        self,
+        timecode: A,
+        buffer: &mut [u8],
+    ) -> Result<&str, fmt::Error>
+    where
+        A: Into<FormatFields>,
+    {
+        let mut writer = ByteWriter { buffer, length: 0 };
+        self.write_fields_to(&mut writer, &timecode.into())?;
+
+        let ByteWriter { buffer, length } = writer;
+
+        // Only whole strings are written, so this is valid UTF-8.
+        str::from_utf8(&buffer[..length]).map_err(|_| fmt::Error)
+    }
+
+    /// Write the timecode in this format to any
+    /// [fmt::Write](https://doc.rust-lang.org/core/fmt/trait.Write.html),
+    /// like a `String`, or a fixed size buffer without the standard
+    /// library.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+    /// let mut s = String::new();
+    /// TimecodeFormat::new().frame_count().write(&mut s, tc).unwrap();
+    /// assert_eq!(s, "900000");
+    /// ```
+    pub fn write<W, A>(self, w: &mut W, timecode: A) -> fmt::Result
+    where
+        W: fmt::Write,
+        A: Into<FormatFields>,
+    {
+        self.write_fields_to(w, &timecode.into())
+    }
+
+    fn write_fields_to<W>(
+        &self,
+        w: &mut W,
+        timecode: &FormatFields,
+    ) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        match self.layout {
+            Layout::Fields => self.write_fields(w, timecode)?,
+            Layout::FrameCount => write!(w, "{}", timecode.frame_number)?,
+            Layout::Seconds(decimals) => {
+                let (numerator, denominator) = timecode.rate;
+                let scale = 10i128.pow(decimals as u32);
+                let value = Rounding::Nearest.divide(
+                    timecode.frame_number as i128
+                        * denominator as i128
+                        * scale,
+                    numerator as i128,
+                );
+
+                write!(w, "{}", value / scale)?;
+
+                if decimals > 0 {
+                    write!(
+                        w,
+                        ".{:0width$}",
+                        value % scale,
+                        width = decimals as usize
+                    )?;
+                }
+            }
+        }
+
+        if self.rate_suffix {
+            let (numerator, denominator) = timecode.rate;
+
+            w.write_char('@')?;
+            write_rate(w, numerator, denominator, timecode.drop_frame)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_fields<W>(
+        &self,
+        w: &mut W,
+        timecode: &FormatFields,
+    ) -> fmt::Result
+    where
+        W: fmt::Write,
+    {
+        let separator = match self.separator {
+            Separator::Standard => Some(':'),
+            Separator::Custom(separator) => Some(separator),
+            Separator::Nothing => None,
+        };
+
+        let frame_separator = match self.separator {
+            _ if self.frame_separator.is_some() => self.frame_separator,
+            Separator::Standard if timecode.drop_frame => Some(';'),
+            _ => separator,
+        };
+
+        if !(self.omit_zero_hours && timecode.hour == 0) {
+            write!(w, "{:02}", timecode.hour)?;
+            write_separator(w, separator)?;
+        }
+
+        write!(w, "{:02}", timecode.minute)?;
+        write_separator(w, separator)?;
+        write!(w, "{:02}", timecode.second)?;
+        write_separator(w, frame_separator)?;
+        write_frame(w, timecode)
+    }
+}
+
+impl Default for TimecodeFormat {
+    fn default() -> TimecodeFormat {
+        TimecodeFormat::new()
+    }
+}
+
+fn write_separator<W>(w: &mut W, separator: Option<char>) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    match separator {
+        Some(separator) => w.write_char(separator),
+        None => Ok(()),
+    }
+}
+
+fn write_frame<W>(w: &mut W, timecode: &FormatFields) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    write!(
+        w,
+        "{:0width$}",
+        timecode.frame,
+        width = frame_digits(timecode.fps)
+    )
+}
+
+/// The fields of a timecode and its frame rate, as written by a
+/// [TimecodeFormat](struct.TimecodeFormat.html). This is made from a
+/// [Timecode](struct.Timecode.html) with any frame rate, or an
+/// [AnyTimecode](struct.AnyTimecode.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatFields {
+    frame_number: u32,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+    fps: u32,
+    drop_frame: bool,
+    rate: (u32, u32),
+}
+
+impl<T> From<Timecode<T>> for FormatFields
+where
+    T: FrameRate,
+{
+    fn from(timecode: Timecode<T>) -> Self {
+        FormatFields {
+            frame_number: timecode.frame_number,
+            hour: timecode.hour,
+            minute: timecode.minute,
+            second: timecode.second,
+            frame: timecode.frame,
+            fps: T::FPS,
+            drop_frame: T::DROP_FRAME,
+            rate: (T::RATE_NUMERATOR, T::RATE_DENOMINATOR),
+        }
+    }
+}
+
+impl From<AnyTimecode> for FormatFields {
+    fn from(timecode: AnyTimecode) -> Self {
+        let frame_rate = timecode.frame_rate();
+
+        FormatFields {
+            frame_number: timecode.frame_number,
+            hour: timecode.hour,
+            minute: timecode.minute,
+            second: timecode.second,
+            frame: timecode.frame,
+            fps: frame_rate.fps(),
+            drop_frame: frame_rate.drop_frame(),
+            rate: frame_rate.rate(),
+        }
+    }
+}
+
+/// A timecode with a [TimecodeFormat](struct.TimecodeFormat.html), returned
+/// by [TimecodeFormat::display](struct.TimecodeFormat.html#method.display).
+///
+/// The `Display` implementation pads the result to the width given in the
+/// format string, and the alternate form, `{:#}`, adds the frame rate like
+/// [rate_suffix](struct.TimecodeFormat.html#method.rate_suffix).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormattedTimecode {
+    format: TimecodeFormat,
+    timecode: FormatFields,
+}
+
+impl fmt::Display for FormattedTimecode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Long enough for any format.
+        let mut buffer = [0; 64];
+
+        let format = if f.alternate() {
+            self.format.rate_suffix()
+        } else {
+            self.format
+        };
+
+        let s = format.write_to(self.timecode, &mut buffer)?;
+
+        f.pad(s)
+    }
+}
+
+/// Writes formatted text into a byte buffer, failing if it does not fit.
+struct ByteWriter<'a> {
+    buffer: &'a mut [u8],
+    length: usize,
+}
+
+impl<'a> fmt::Write for ByteWriter<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.length + s.len();
+
+        if end > self.buffer.len() {
+            return Err(fmt::Error);
+        }
+
+        self.buffer[self.length..end].copy_from_slice(s.as_bytes());
+        self.length = end;
+
+        Ok(())
+    }
+}
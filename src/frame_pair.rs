@@ -0,0 +1,96 @@
+use {FrameRate, FrameRate2398, FrameRate24, FrameRate25, FrameRate2997,
+     FrameRate2997Ndf, FrameRate30, FrameRate4795, FrameRate48, FrameRate50,
+     FrameRate5994, FrameRate5994Ndf, FrameRate60, Timecode};
+
+/// Frame rates that SMPTE ST 12-1 carries as pairs of frames at half the
+/// frame rate, with a flag telling the two frames of a pair apart.
+///
+/// Transports such as LTC and VITC only have room for frame numbers up to
+/// 29, so a 50 fps frame is sent as a 25 fps timecode and a field flag.
+pub trait PairedFrameRate: FrameRate {
+    /// The frame rate of the frame pairs.
+    type PairRate: FrameRate;
+}
+
+macro_rules! impl_paired_frame_rate {
+    ($frame_rate:ident, $pair_rate:ident) => {
+        impl PairedFrameRate for $frame_rate {
+            type PairRate = $pair_rate;
+        }
+
+        const _: () = assert!(
+            $frame_rate::FPS == 2 * $pair_rate::FPS
+                && $frame_rate::DROP_FRAME == $pair_rate::DROP_FRAME
+        );
+    };
+}
+
+impl_paired_frame_rate!(FrameRate48, FrameRate24);
+impl_paired_frame_rate!(FrameRate4795, FrameRate2398);
+impl_paired_frame_rate!(FrameRate50, FrameRate25);
+impl_paired_frame_rate!(FrameRate5994, FrameRate2997);
+impl_paired_frame_rate!(FrameRate5994Ndf, FrameRate2997Ndf);
+impl_paired_frame_rate!(FrameRate60, FrameRate30);
+
+/// A frame at a high frame rate, as a timecode for the pair of frames it is
+/// in and a flag for which frame of the pair it is.
+///
+/// Returned by [Timecode::frame_pair](struct.Timecode.html#method.frame_pair).
+#[derive(Debug, PartialEq)]
+pub struct FramePair<FrameRate> {
+    /// The timecode of the frame pair, where the frame field is the pair
+    /// number.
+    pub timecode: Timecode<FrameRate>,
+
+    /// The field flag, set for the second frame of the pair.
+    pub field_flag: bool,
+}
+
+impl<T> Clone for FramePair<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FramePair<T> {}
+
+impl<T> Timecode<T>
+where
+    T: PairedFrameRate,
+{
+    /// Returns the frame pair this timecode is in, and which frame of the
+    /// pair it is.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate50>::new(10, 0, 0, 49).unwrap();
+    /// let pair = tc.frame_pair();
+    /// assert_eq!(pair.timecode.to_string(), "10:00:00:24");
+    /// assert!(pair.field_flag);
+    ///
+    /// assert_eq!(Timecode::from_frame_pair(pair), tc);
+    /// ```
+    pub fn frame_pair(self) -> FramePair<T::PairRate> {
+        FramePair {
+            timecode: Timecode::from(self.frame_number / 2),
+            field_flag: self.frame_number % 2 == 1,
+        }
+    }
+
+    /// Returns the timecode of a frame in a frame pair.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let pair = FramePair {
+    ///     timecode: Timecode::<FrameRate2997>::new(0, 1, 0, 2).unwrap(),
+    ///     field_flag: false,
+    /// };
+    /// let tc = Timecode::<FrameRate5994>::from_frame_pair(pair);
+    /// assert_eq!(tc.to_string(), "00:01:00;04");
+    /// ```
+    pub fn from_frame_pair(pair: FramePair<T::PairRate>) -> Timecode<T> {
+        Timecode::from(pair.timecode.frame_number * 2 + pair.field_flag as u32)
+    }
+}
@@ -1,9 +1,39 @@
+use core::fmt;
+use core::str;
+use core::time::Duration;
+
+use Rounding;
+use TimecodeError;
+use TimecodeErrorKind;
+
 /// Trait describing frame rates.
 pub trait FrameRate {
     const FPS: u32;
 
     const DROP_FRAME: bool;
 
+    /// Numerator of the exact frame rate, in frames per second.
+    ///
+    /// For most frame rates this is the same as `FPS`, but for instance
+    /// 23.976 fps is 24000/1001 frames per second. By default it is `FPS`,
+    /// or `FPS * 1000` for drop frame frame rates.
+    const RATE_NUMERATOR: u32 = if Self::DROP_FRAME {
+        Self::FPS * 1000
+    } else {
+        Self::FPS
+    };
+
+    /// Denominator of the exact frame rate, in frames per second. By
+    /// default it is 1, or 1001 for drop frame frame rates.
+    const RATE_DENOMINATOR: u32 = if Self::DROP_FRAME { 1001 } else { 1 };
+
+    /// The runtime value corresponding to this frame rate, if there is one.
+    ///
+    /// This is needed to convert timecodes to
+    /// [AnyTimecode](struct.AnyTimecode.html). By default it is `None`, for
+    /// frame rates defined outside of this crate.
+    const ANY_FRAME_RATE: Option<AnyFrameRate> = None;
+
     #[doc(hidden)]
     const MAX_FRAMES: u32;
 
@@ -13,9 +43,61 @@ pub trait FrameRate {
     #[doc(hidden)]
     const FRAMES_PER_HOUR: u32 = Self::FRAMES_PER_MINUTE * 60;
 
+    /// For drop frame frame rates, the number of frame labels skipped at
+    /// the start of each minute that is not a multiple of ten. This is two
+    /// labels for every 30 frames per second.
     #[doc(hidden)]
     const DROP_FRAME_COUNT: u32 = Self::FPS / 15;
 
+    /// Real time duration of one frame, rounded to the nearest nanosecond.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(FrameRate25::frame_duration(), Duration::from_millis(40));
+    /// assert_eq!(
+    ///     FrameRate2997::frame_duration(),
+    ///     Duration::from_nanos(33_366_667)
+    /// );
+    /// ```
+    fn frame_duration() -> Duration {
+        Self::frames_to_duration(1)
+    }
+
+    /// Real time duration of a number of frames, rounded to the nearest
+    /// nanosecond.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::time::Duration;
+    ///
+    /// // One hour of frames at 23.976 fps takes longer than an hour.
+    /// assert_eq!(
+    ///     FrameRate2398::frames_to_duration(86400),
+    ///     Duration::from_millis(3_603_600)
+    /// );
+    /// ```
+    fn frames_to_duration(frames: u64) -> Duration {
+        duration_of_frames(
+            frames,
+            Self::RATE_NUMERATOR,
+            Self::RATE_DENOMINATOR,
+        )
+    }
+
+    /// Real time duration of a number of frames, in seconds.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// assert_eq!(FrameRate5994::frames_to_seconds(60), 1.001);
+    /// ```
+    fn frames_to_seconds(frames: u64) -> f64 {
+        frames as f64 * Self::RATE_DENOMINATOR as f64
+            / Self::RATE_NUMERATOR as f64
+    }
+
     /// Given the elements of a timecode, calculate the frame offset from zero.
     #[doc(hidden)]
     fn calculate_frame_number(
@@ -24,7 +106,7 @@ pub trait FrameRate {
         second: u32,
         frame: u32,
     ) -> Option<u32> {
-        if hour > 23 || minute > 59 || second > 59 || frame > Self::FPS
+        if hour > 23 || minute > 59 || second > 59 || frame >= Self::FPS
             || (Self::DROP_FRAME && second == 0 && minute % 10 != 0
                 && frame < Self::DROP_FRAME_COUNT)
         {
@@ -32,21 +114,16 @@ pub trait FrameRate {
         }
 
         let frame_number_before_drop_frames =
-            (Self::FRAMES_PER_HOUR * hour as u32)
-                + (Self::FRAMES_PER_MINUTE * minute as u32)
-                + (Self::FPS * second as u32) + frame as u32;
+            (Self::FRAMES_PER_HOUR * hour)
+                + (Self::FRAMES_PER_MINUTE * minute)
+                + (Self::FPS * second) + frame;
 
         let frame_number = if Self::DROP_FRAME {
             let tens = hour * 6 + minute / 10;
             let minutes_without_tens = minute % 10;
-            let drop_frame_minutes_without_tens = if minutes_without_tens > 1 {
-                minutes_without_tens
-            } else {
-                0
-            };
             let drop_frames_per_ten = Self::DROP_FRAME_COUNT * 9;
             frame_number_before_drop_frames - (tens * drop_frames_per_ten)
-                - drop_frame_minutes_without_tens * Self::DROP_FRAME_COUNT
+                - minutes_without_tens * Self::DROP_FRAME_COUNT
         } else {
             frame_number_before_drop_frames
         };
@@ -57,7 +134,7 @@ pub trait FrameRate {
     /// Given a frame number, calculate the fields for a time code.
     #[doc(hidden)]
     fn calculate_time_code(frame_number: u32) -> (u8, u8, u8, u8) {
-        if frame_number > Self::MAX_FRAMES {
+        if frame_number >= Self::MAX_FRAMES {
             panic!(
                 "Frame rate only supports up to {:?} frames.",
                 Self::MAX_FRAMES
@@ -125,48 +202,453 @@ pub trait FrameRate {
     }
 }
 
+/// Real time duration of a number of frames at `numerator / denominator`
+/// frames per second, rounded to the nearest nanosecond.
+fn duration_of_frames(
+    frames: u64,
+    numerator: u32,
+    denominator: u32,
+) -> Duration {
+    let numerator = numerator as u128;
+    let nanos = (frames as u128 * denominator as u128 * 1_000_000_000
+        + numerator / 2)
+        / numerator;
+
+    Duration::new(
+        (nanos / 1_000_000_000) as u64,
+        (nanos % 1_000_000_000) as u32,
+    )
+}
+
 macro_rules! create_frame_rate {
-    ($frame_rate_name:ident, $frame_rate:expr, false) => (
+    ($frame_rate_name:ident, $frame_rate:expr, $numerator:expr,
+     $denominator:expr, false, $any:ident) => (
         #[derive(Debug, PartialEq)]
         pub struct $frame_rate_name;
 
         impl FrameRate for $frame_rate_name {
             const FPS: u32 = $frame_rate;
             const DROP_FRAME: bool = false;
+            const RATE_NUMERATOR: u32 = $numerator;
+            const RATE_DENOMINATOR: u32 = $denominator;
+            const ANY_FRAME_RATE: Option<AnyFrameRate> =
+                Some(AnyFrameRate::$any);
             const MAX_FRAMES: u32 = 86400 * Self::FPS;
         }
     );
-    ($frame_rate_name:ident, $frame_rate:expr, true) => (
+    ($frame_rate_name:ident, $frame_rate:expr, $numerator:expr,
+     $denominator:expr, true, $any:ident) => (
         #[derive(Debug, PartialEq)]
         pub struct $frame_rate_name;
 
         impl FrameRate for $frame_rate_name {
             const FPS: u32 = $frame_rate;
             const DROP_FRAME: bool = true;
-            const MAX_FRAMES: u32 = 86400 * Self::FPS
-                - 144 * (18 * (Self::FPS / 30));
+            const RATE_NUMERATOR: u32 = $numerator;
+            const RATE_DENOMINATOR: u32 = $denominator;
+            const ANY_FRAME_RATE: Option<AnyFrameRate> =
+                Some(AnyFrameRate::$any);
+            // Frames are dropped in 54 of the 60 minutes of each hour.
+            const MAX_FRAMES: u32 =
+                86400 * Self::FPS - 24 * 54 * Self::DROP_FRAME_COUNT;
         }
+
+        // Drop frame counting only works for multiples of 30 fps.
+        const _: () = assert!($frame_rate % 30 == 0);
     );
 }
 
-create_frame_rate!(FrameRate24, 24, false);
-create_frame_rate!(FrameRate25, 25, false);
-create_frame_rate!(FrameRate30, 30, false);
-create_frame_rate!(FrameRate50, 50, false);
-create_frame_rate!(FrameRate60, 60, false);
-create_frame_rate!(FrameRate2398, 24, false);
-create_frame_rate!(FrameRate2997, 30, true);
-create_frame_rate!(FrameRate5994, 60, true);
-
-pub trait NormalizeFrameNumber<T> {
-    fn normalize(self, max_frames: T) -> u32;
+create_frame_rate!(FrameRate24, 24, 24, 1, false, Fps24);
+create_frame_rate!(FrameRate25, 25, 25, 1, false, Fps25);
+create_frame_rate!(FrameRate30, 30, 30, 1, false, Fps30);
+create_frame_rate!(FrameRate50, 50, 50, 1, false, Fps50);
+create_frame_rate!(FrameRate60, 60, 60, 1, false, Fps60);
+create_frame_rate!(FrameRate2398, 24, 24000, 1001, false, Fps2398);
+create_frame_rate!(FrameRate2997, 30, 30000, 1001, true, Fps2997);
+create_frame_rate!(FrameRate5994, 60, 60000, 1001, true, Fps5994);
+create_frame_rate!(FrameRate2997Ndf, 30, 30000, 1001, false, Fps2997Ndf);
+create_frame_rate!(FrameRate5994Ndf, 60, 60000, 1001, false, Fps5994Ndf);
+create_frame_rate!(FrameRate48, 48, 48, 1, false, Fps48);
+create_frame_rate!(FrameRate4795, 48, 48000, 1001, false, Fps4795);
+create_frame_rate!(FrameRate72, 72, 72, 1, false, Fps72);
+create_frame_rate!(FrameRate96, 96, 96, 1, false, Fps96);
+create_frame_rate!(FrameRate100, 100, 100, 1, false, Fps100);
+create_frame_rate!(FrameRate11988, 120, 120000, 1001, true, Fps11988);
+create_frame_rate!(FrameRate11988Ndf, 120, 120000, 1001, false, Fps11988Ndf);
+create_frame_rate!(FrameRate120, 120, 120, 1, false, Fps120);
+
+/// A frame rate selected at runtime, for use with
+/// [AnyTimecode](struct.AnyTimecode.html).
+///
+/// Each variant corresponds to one of the types implementing
+/// [FrameRate](trait.FrameRate.html).
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let frame_rate = AnyFrameRate::new(30000, 1001, true).unwrap();
+/// assert_eq!(frame_rate, AnyFrameRate::Fps2997);
+/// assert_eq!(frame_rate.fps(), 30);
+/// assert_eq!(frame_rate.to_string(), "29.97DF");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnyFrameRate {
+    /// See [FrameRate24](struct.FrameRate24.html).
+    Fps24,
+    /// See [FrameRate25](struct.FrameRate25.html).
+    Fps25,
+    /// See [FrameRate30](struct.FrameRate30.html).
+    Fps30,
+    /// See [FrameRate50](struct.FrameRate50.html).
+    Fps50,
+    /// See [FrameRate60](struct.FrameRate60.html).
+    Fps60,
+    /// See [FrameRate2398](struct.FrameRate2398.html).
+    Fps2398,
+    /// See [FrameRate2997](struct.FrameRate2997.html).
+    Fps2997,
+    /// See [FrameRate5994](struct.FrameRate5994.html).
+    Fps5994,
+    /// See [FrameRate2997Ndf](struct.FrameRate2997Ndf.html).
+    Fps2997Ndf,
+    /// See [FrameRate5994Ndf](struct.FrameRate5994Ndf.html).
+    Fps5994Ndf,
+    /// See [FrameRate48](struct.FrameRate48.html).
+    Fps48,
+    /// See [FrameRate4795](struct.FrameRate4795.html).
+    Fps4795,
+    /// See [FrameRate72](struct.FrameRate72.html).
+    Fps72,
+    /// See [FrameRate96](struct.FrameRate96.html).
+    Fps96,
+    /// See [FrameRate100](struct.FrameRate100.html).
+    Fps100,
+    /// See [FrameRate11988](struct.FrameRate11988.html).
+    Fps11988,
+    /// See [FrameRate11988Ndf](struct.FrameRate11988Ndf.html).
+    Fps11988Ndf,
+    /// See [FrameRate120](struct.FrameRate120.html).
+    Fps120,
+}
+
+/// Evaluate an expression with `$r` bound to the
+/// [FrameRate](trait.FrameRate.html) type matching an
+/// [AnyFrameRate](enum.AnyFrameRate.html) value.
+macro_rules! with_frame_rate {
+    ($any:expr, $r:ident => $body:expr) => {
+        match $any {
+            $crate::AnyFrameRate::Fps24 => {
+                type $r = $crate::FrameRate24;
+                $body
+            }
+            $crate::AnyFrameRate::Fps25 => {
+                type $r = $crate::FrameRate25;
+                $body
+            }
+            $crate::AnyFrameRate::Fps30 => {
+                type $r = $crate::FrameRate30;
+                $body
+            }
+            $crate::AnyFrameRate::Fps50 => {
+                type $r = $crate::FrameRate50;
+                $body
+            }
+            $crate::AnyFrameRate::Fps60 => {
+                type $r = $crate::FrameRate60;
+                $body
+            }
+            $crate::AnyFrameRate::Fps2398 => {
+                type $r = $crate::FrameRate2398;
+                $body
+            }
+            $crate::AnyFrameRate::Fps2997 => {
+                type $r = $crate::FrameRate2997;
+                $body
+            }
+            $crate::AnyFrameRate::Fps5994 => {
+                type $r = $crate::FrameRate5994;
+                $body
+            }
+            $crate::AnyFrameRate::Fps2997Ndf => {
+                type $r = $crate::FrameRate2997Ndf;
+                $body
+            }
+            $crate::AnyFrameRate::Fps5994Ndf => {
+                type $r = $crate::FrameRate5994Ndf;
+                $body
+            }
+            $crate::AnyFrameRate::Fps48 => {
+                type $r = $crate::FrameRate48;
+                $body
+            }
+            $crate::AnyFrameRate::Fps4795 => {
+                type $r = $crate::FrameRate4795;
+                $body
+            }
+            $crate::AnyFrameRate::Fps72 => {
+                type $r = $crate::FrameRate72;
+                $body
+            }
+            $crate::AnyFrameRate::Fps96 => {
+                type $r = $crate::FrameRate96;
+                $body
+            }
+            $crate::AnyFrameRate::Fps100 => {
+                type $r = $crate::FrameRate100;
+                $body
+            }
+            $crate::AnyFrameRate::Fps11988 => {
+                type $r = $crate::FrameRate11988;
+                $body
+            }
+            $crate::AnyFrameRate::Fps11988Ndf => {
+                type $r = $crate::FrameRate11988Ndf;
+                $body
+            }
+            $crate::AnyFrameRate::Fps120 => {
+                type $r = $crate::FrameRate120;
+                $body
+            }
+        }
+    };
+}
+
+impl AnyFrameRate {
+    /// All supported frame rates.
+    pub const ALL: &'static [AnyFrameRate] = &[
+        AnyFrameRate::Fps24,
+        AnyFrameRate::Fps25,
+        AnyFrameRate::Fps30,
+        AnyFrameRate::Fps50,
+        AnyFrameRate::Fps60,
+        AnyFrameRate::Fps2398,
+        AnyFrameRate::Fps2997,
+        AnyFrameRate::Fps5994,
+        AnyFrameRate::Fps2997Ndf,
+        AnyFrameRate::Fps5994Ndf,
+        AnyFrameRate::Fps48,
+        AnyFrameRate::Fps4795,
+        AnyFrameRate::Fps72,
+        AnyFrameRate::Fps96,
+        AnyFrameRate::Fps100,
+        AnyFrameRate::Fps11988,
+        AnyFrameRate::Fps11988Ndf,
+        AnyFrameRate::Fps120,
+    ];
+
+    /// Find the frame rate with the given exact rate, expressed as
+    /// `numerator / denominator` frames per second, and drop frame setting.
+    ///
+    /// The fraction does not have to be reduced, so both `24000 / 1001` and
+    /// `48000 / 2002` will give 23.976 fps.
+    pub fn new(
+        numerator: u32,
+        denominator: u32,
+        drop_frame: bool,
+    ) -> Option<AnyFrameRate> {
+        if denominator == 0 {
+            return None;
+        }
+
+        AnyFrameRate::ALL.iter().cloned().find(|frame_rate| {
+            let (n, d) = frame_rate.rate();
+            frame_rate.drop_frame() == drop_frame
+                && n as u64 * denominator as u64
+                    == numerator as u64 * d as u64
+        })
+    }
+
+    /// Get the runtime value for a [FrameRate](trait.FrameRate.html) type,
+    /// if it has one.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// assert_eq!(
+    ///     AnyFrameRate::of::<FrameRate25>(),
+    ///     Some(AnyFrameRate::Fps25)
+    /// );
+    /// ```
+    pub fn of<T>() -> Option<AnyFrameRate>
+    where
+        T: FrameRate,
+    {
+        T::ANY_FRAME_RATE
+    }
+
+    /// Nominal number of frames per second, as used for counting frames in
+    /// a timecode.
+    pub fn fps(self) -> u32 {
+        with_frame_rate!(self, R => R::FPS)
+    }
+
+    /// Whether this is a drop frame frame rate.
+    pub const fn drop_frame(self) -> bool {
+        with_frame_rate!(self, R => R::DROP_FRAME)
+    }
+
+    /// The exact rate, as a `(numerator, denominator)` pair of frames per
+    /// second.
+    pub const fn rate(self) -> (u32, u32) {
+        with_frame_rate!(self, R => (R::RATE_NUMERATOR, R::RATE_DENOMINATOR))
+    }
+
+    /// Real time duration of one frame, rounded to the nearest nanosecond.
+    pub fn frame_duration(self) -> Duration {
+        with_frame_rate!(self, R => R::frame_duration())
+    }
+
+    /// Real time duration of a number of frames, rounded to the nearest
+    /// nanosecond.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::time::Duration;
+    ///
+    /// assert_eq!(
+    ///     AnyFrameRate::Fps2997.frames_to_duration(30),
+    ///     Duration::from_millis(1001)
+    /// );
+    /// ```
+    pub fn frames_to_duration(self, frames: u64) -> Duration {
+        with_frame_rate!(self, R => R::frames_to_duration(frames))
+    }
+
+    /// Real time duration of a number of frames, in seconds.
+    pub fn frames_to_seconds(self, frames: u64) -> f64 {
+        with_frame_rate!(self, R => R::frames_to_seconds(frames))
+    }
+
+    #[doc(hidden)]
+    pub fn max_frames(self) -> u32 {
+        with_frame_rate!(self, R => R::MAX_FRAMES)
+    }
+}
+
+/// Format the frame rate as its rate in frames per second, with a `DF`
+/// suffix for drop frame rates, and an `NDF` suffix for non drop frame
+/// 29.97, 59.94 and 119.88 fps.
+impl fmt::Display for AnyFrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (numerator, denominator) = self.rate();
+
+        write_rate(f, numerator, denominator, self.drop_frame())
+    }
+}
+
+/// Write an exact frame rate with up to three decimals, like `23.976`,
+/// followed by `DF` for drop frame, or by `NDF` for the non drop frame
+/// versions of rates that have a drop frame version, like `29.97NDF`.
+pub(crate) fn write_rate<W>(
+    w: &mut W,
+    numerator: u32,
+    denominator: u32,
+    drop_frame: bool,
+) -> fmt::Result
+where
+    W: fmt::Write,
+{
+    let thousandths = Rounding::Nearest
+        .divide(numerator as i128 * 1000, denominator as i128);
+
+    write!(w, "{}", thousandths / 1000)?;
+
+    let mut fraction = thousandths % 1000;
+    let mut digits = 3;
+
+    if fraction != 0 {
+        while fraction % 10 == 0 {
+            fraction /= 10;
+            digits -= 1;
+        }
+
+        write!(w, ".{:0width$}", fraction, width = digits)?;
+    }
+
+    // Only multiples of 30 fps have drop frame versions.
+    if drop_frame {
+        w.write_str("DF")
+    } else if thousandths % 1000 != 0 && (thousandths + 500) / 1000 % 30 == 0
+    {
+        w.write_str("NDF")
+    } else {
+        Ok(())
+    }
+}
+
+/// Parse a frame rate, in the format written by its `Display`
+/// implementation.
+///
+/// The `DF` or `NDF` suffix may be preceded by a space, and `23.98` and
+/// `47.95` are accepted as well as `23.976` and `47.952`. Without a suffix,
+/// 29.97, 59.94 and 119.88 fps are taken to be drop frame.
+///
+/// ```
+/// use video_timecode::*;
+/// use std::str::FromStr;
+///
+/// assert_eq!(AnyFrameRate::from_str("25").unwrap(), AnyFrameRate::Fps25);
+/// assert_eq!(
+///     AnyFrameRate::from_str("29.97 DF").unwrap(),
+///     AnyFrameRate::Fps2997
+/// );
+/// assert_eq!(
+///     AnyFrameRate::from_str("29.97NDF").unwrap(),
+///     AnyFrameRate::Fps2997Ndf
+/// );
+/// ```
+impl str::FromStr for AnyFrameRate {
+    type Err = TimecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::AnyFrameRate::*;
+
+        let (s, drop_frame) = if let Some(rest) = s.strip_suffix("NDF") {
+            (rest.trim_end(), Some(false))
+        } else if let Some(rest) = s.strip_suffix("DF") {
+            (rest.trim_end(), Some(true))
+        } else {
+            (s, None)
+        };
+
+        let (non_drop_frame_rate, drop_frame_rate) = match s {
+            "24" => (Some(Fps24), None),
+            "25" => (Some(Fps25), None),
+            "30" => (Some(Fps30), None),
+            "50" => (Some(Fps50), None),
+            "60" => (Some(Fps60), None),
+            "23.976" | "23.98" => (Some(Fps2398), None),
+            "29.97" => (Some(Fps2997Ndf), Some(Fps2997)),
+            "59.94" => (Some(Fps5994Ndf), Some(Fps5994)),
+            "48" => (Some(Fps48), None),
+            "47.952" | "47.95" => (Some(Fps4795), None),
+            "72" => (Some(Fps72), None),
+            "96" => (Some(Fps96), None),
+            "100" => (Some(Fps100), None),
+            "119.88" => (Some(Fps11988Ndf), Some(Fps11988)),
+            "120" => (Some(Fps120), None),
+            _ => (None, None),
+        };
+
+        let frame_rate = match drop_frame {
+            Some(true) => drop_frame_rate,
+            Some(false) => non_drop_frame_rate,
+            None => drop_frame_rate.or(non_drop_frame_rate),
+        };
+
+        frame_rate.ok_or(TimecodeError::from(TimecodeErrorKind::InvalidFormat))
+    }
+}
+
+pub trait NormalizeFrameNumber {
+    fn normalize(self, max_frames: u32) -> u32;
 }
 
 macro_rules! impl_int_unsigned {
     ($($t:ty)*) => ($(
-        impl NormalizeFrameNumber<$t> for $t {
-            fn normalize(self, max_frames: $t) -> u32 {
-                (self % max_frames) as u32
+        impl NormalizeFrameNumber for $t {
+            fn normalize(self, max_frames: u32) -> u32 {
+                (self as u64 % max_frames as u64) as u32
             }
         }
     )*)
@@ -175,17 +657,15 @@ impl_int_unsigned! { usize u8 u16 u32 u64 }
 
 macro_rules! impl_int_signed {
     ($($t:ty)*) => ($(
-        impl NormalizeFrameNumber<$t> for $t {
-            fn normalize(self, max_frames: $t) -> u32 {
-                let remainder = self % max_frames;
-
-                let result = if remainder < 0 {
-                    remainder + max_frames
-                } else {
-                    remainder
-                };
-
-                result as u32
+        impl NormalizeFrameNumber for $t {
+            fn normalize(self, max_frames: u32) -> u32 {
+                assert!(
+                    self >= 0,
+                    "Frame number can not be negative. Use a \
+                     TimecodeDuration for signed offsets."
+                );
+
+                (self as u64 % max_frames as u64) as u32
             }
         }
     )*)
@@ -0,0 +1,181 @@
+use core::iter;
+
+use {FrameRate, Timecode, TimecodeDuration};
+
+impl<T> Timecode<T>
+where
+    T: FrameRate,
+{
+    /// Iterate over the timecodes of each frame from this timecode on.
+    ///
+    /// The timecode fields are stepped one frame at a time, skipping dropped
+    /// frames, which is faster than calculating each timecode from its frame
+    /// number. The iterator rolls over at 24 hours and never ends, so use
+    /// [take](https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.take)
+    /// or similar to limit it.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate2997>::new(0, 0, 59, 28).unwrap();
+    /// let frames: Vec<String> =
+    ///     tc.frames().take(3).map(|tc| tc.to_string()).collect();
+    /// assert_eq!(frames, ["00:00:59;28", "00:00:59;29", "00:01:00;02"]);
+    /// ```
+    pub fn frames(self) -> Frames<T> {
+        Frames {
+            next: self,
+            step: 1,
+        }
+    }
+
+    /// Iterate backward over the timecodes of each frame from this timecode.
+    ///
+    /// Like [frames](#method.frames), this rolls over at 24 hours and never
+    /// ends.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate25>::new(0, 0, 0, 1).unwrap();
+    /// let frames: Vec<String> =
+    ///     tc.frames_rev().take(3).map(|tc| tc.to_string()).collect();
+    /// assert_eq!(frames, ["00:00:00:01", "00:00:00:00", "23:59:59:24"]);
+    /// ```
+    pub fn frames_rev(self) -> Frames<T> {
+        Frames {
+            next: self,
+            step: -1,
+        }
+    }
+
+    /// Iterate over timecodes from this timecode on, moving by the given
+    /// duration at each step. A negative duration moves backward.
+    ///
+    /// Like [frames](#method.frames), this rolls over at 24 hours and never
+    /// ends.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate24>::new(0, 0, 0, 0).unwrap();
+    /// let step = TimecodeDuration::from_frames(12);
+    /// let frames: Vec<String> = tc
+    ///     .step_by_duration(step)
+    ///     .take(3)
+    ///     .map(|tc| tc.to_string())
+    ///     .collect();
+    /// assert_eq!(frames, ["00:00:00:00", "00:00:00:12", "00:00:01:00"]);
+    /// ```
+    pub fn step_by_duration(self, step: TimecodeDuration<T>) -> Frames<T> {
+        Frames {
+            next: self,
+            step: step.frames(),
+        }
+    }
+
+    /// Move to the next frame, rolling over at 24 hours.
+    pub(crate) fn increment(&mut self) {
+        self.frame_number += 1;
+
+        if self.frame_number == T::MAX_FRAMES {
+            *self = Timecode::from(0u32);
+            return;
+        }
+
+        self.frame += 1;
+
+        if self.frame as u32 == T::FPS {
+            self.frame = 0;
+            self.second += 1;
+
+            if self.second == 60 {
+                self.second = 0;
+                self.minute += 1;
+
+                if self.minute == 60 {
+                    self.minute = 0;
+                    self.hour += 1;
+                }
+
+                if T::DROP_FRAME && self.minute % 10 != 0 {
+                    self.frame = T::DROP_FRAME_COUNT as u8;
+                }
+            }
+        }
+    }
+
+    /// Move to the previous frame, rolling over at 24 hours.
+    pub(crate) fn decrement(&mut self) {
+        if self.frame_number == 0 {
+            *self = Timecode::from(T::MAX_FRAMES - 1);
+            return;
+        }
+
+        self.frame_number -= 1;
+
+        let first_frame = if T::DROP_FRAME
+            && self.second == 0
+            && self.minute % 10 != 0
+        {
+            T::DROP_FRAME_COUNT as u8
+        } else {
+            0
+        };
+
+        if self.frame > first_frame {
+            self.frame -= 1;
+            return;
+        }
+
+        self.frame = (T::FPS - 1) as u8;
+
+        if self.second > 0 {
+            self.second -= 1;
+            return;
+        }
+
+        self.second = 59;
+
+        if self.minute > 0 {
+            self.minute -= 1;
+        } else {
+            self.minute = 59;
+            self.hour -= 1;
+        }
+    }
+}
+
+/// Iterator over timecodes at a fixed distance from each other, returned by
+/// [Timecode::frames](struct.Timecode.html#method.frames) and similar
+/// methods.
+#[derive(Debug)]
+pub struct Frames<T> {
+    next: Timecode<T>,
+    step: i64,
+}
+
+impl<T> Iterator for Frames<T>
+where
+    T: FrameRate,
+{
+    type Item = Timecode<T>;
+
+    fn next(&mut self) -> Option<Timecode<T>> {
+        let timecode = self.next;
+
+        match self.step {
+            1 => self.next.increment(),
+            -1 => self.next.decrement(),
+            step => self.next = self.next.wrapping_add(step),
+        }
+
+        Some(timecode)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<T> iter::FusedIterator for Frames<T> where T: FrameRate {}
@@ -1,34 +1,68 @@
 //!  A library for manipulating SMPTE timecodes.
+//!
+//! The library does not allocate, and can be used without the standard
+//! library by turning off the default `std` feature. Timecodes can then be
+//! formatted into any
+//! [fmt::Write](https://doc.rust-lang.org/core/fmt/trait.Write.html), or a
+//! byte buffer with
+//! [TimecodeFormat::write_to](struct.TimecodeFormat.html#method.write_to).
 
-use std::fmt;
-use std::marker;
-use std::ops;
-use std::str;
+#![no_std]
 
-mod frame_rate;
-
-pub use frame_rate::{FrameRate, FrameRate2398, FrameRate24, FrameRate25,
-                     FrameRate2997, FrameRate30, FrameRate50, FrameRate5994,
-                     FrameRate60};
-use frame_rate::NormalizeFrameNumber;
+#[cfg(feature = "std")]
+extern crate std;
 
-#[derive(Debug)]
-/// All [Result](https://doc.rust-lang.org/std/result/enum.Result.html) values
-/// returned by this library will use this for error values.
-pub struct TimecodeError {
-    pub kind: TimecodeErrorKind,
-}
+use core::fmt;
+use core::marker;
+use core::ops;
+use core::str;
 
-#[derive(Debug)]
-/// Error values for this library.
-pub enum TimecodeErrorKind {
-    /// Timecode parsing failed due to input having an invalid format.
-    InvalidFormat,
+#[cfg(feature = "serde")]
+extern crate serde as serde_crate;
 
-    /// Timecode had an invalid value. For instance the frame field might have
-    /// a value higher than the frame rate allows.
-    InvalidTimecode
-}
+#[macro_use]
+mod frame_rate;
+mod any_timecode;
+mod audio;
+mod conversion;
+mod error;
+mod extended_timecode;
+pub mod film;
+mod format;
+mod frame_pair;
+mod frames;
+pub mod ltc;
+mod parser;
+pub mod pulldown;
+mod range;
+mod real_time;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod subframe_timecode;
+mod timecode_duration;
+pub mod vitc;
+
+pub use any_timecode::AnyTimecode;
+pub use conversion::{Conversion, DroppedFramePolicy};
+pub use error::{TimecodeError, TimecodeErrorKind, TimecodeErrorReason,
+                TimecodeField};
+pub use extended_timecode::ExtendedTimecode;
+pub use format::{FormatFields, FormattedTimecode, TimecodeFormat};
+pub use frame_pair::{FramePair, PairedFrameRate};
+pub use frames::Frames;
+pub use frame_rate::{AnyFrameRate, FrameRate, FrameRate100, FrameRate11988,
+                     FrameRate11988Ndf, FrameRate120, FrameRate2398,
+                     FrameRate24, FrameRate25, FrameRate2997,
+                     FrameRate2997Ndf, FrameRate30, FrameRate48,
+                     FrameRate4795, FrameRate50, FrameRate5994,
+                     FrameRate5994Ndf, FrameRate60, FrameRate72,
+                     FrameRate96};
+use frame_rate::NormalizeFrameNumber;
+pub use parser::{ParsedTimecode, Syntax, TimecodeParser};
+pub use range::{TimecodeRange, TimecodeRangeIter};
+pub use real_time::Rounding;
+pub use subframe_timecode::SubframeTimecode;
+pub use timecode_duration::TimecodeDuration;
 
 /// Representation of a timecode as a struct, generic over types implementing
 /// the trait [FrameRate](trait.FrameRate.html).
@@ -71,6 +105,14 @@ pub struct Timecode<FrameRate> {
     frame_rate: marker::PhantomData<FrameRate>,
 }
 
+impl<T> Clone for Timecode<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Timecode<T> {}
+
 impl<T> Timecode<T> {
     /// Returns a timecode with the given hour/minute/second/frame fields.
     ///
@@ -89,8 +131,6 @@ impl<T> Timecode<T> {
     where
         T: FrameRate,
     {
-        use self::TimecodeErrorKind::*;
-
         let result = T::calculate_frame_number(
             hour as u32,
             minute as u32,
@@ -107,11 +147,129 @@ impl<T> Timecode<T> {
                 frame,
                 frame_rate: marker::PhantomData,
             }),
-            None => Err(TimecodeError {
-                kind: InvalidTimecode,
-            }),
+            None => Err(invalid_fields(
+                T::FPS,
+                T::DROP_FRAME,
+                hour,
+                minute,
+                second,
+                frame,
+            )),
+        }
+    }
+
+    /// Add a number of frames to a timecode, returning `None` instead of
+    /// rolling over if the result is before `00:00:00:00` or at or after 24
+    /// hours.
+    ///
+    /// To add a [TimecodeDuration](struct.TimecodeDuration.html), pass its
+    /// number of frames.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate25>::new(23, 59, 59, 20).unwrap();
+    /// assert_eq!(
+    ///     tc.checked_add(4),
+    ///     Some(Timecode::<FrameRate25>::new(23, 59, 59, 24).unwrap())
+    /// );
+    /// assert_eq!(tc.checked_add(5), None);
+    /// ```
+    pub fn checked_add(self, frames: i64) -> Option<Self>
+    where
+        T: FrameRate,
+    {
+        let frame_number = self.frame_number as i128 + frames as i128;
+
+        if frame_number >= 0 && frame_number < T::MAX_FRAMES as i128 {
+            Some(Timecode::from(frame_number as u32))
+        } else {
+            None
         }
     }
+
+    /// Remove a number of frames from a timecode, returning `None` instead
+    /// of rolling over if the result is before `00:00:00:00` or at or after
+    /// 24 hours.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate25>::new(0, 0, 0, 10).unwrap();
+    /// assert_eq!(tc.checked_sub(10).unwrap().frame_number, 0);
+    /// assert_eq!(tc.checked_sub(11), None);
+    /// ```
+    pub fn checked_sub(self, frames: i64) -> Option<Self>
+    where
+        T: FrameRate,
+    {
+        self.checked_add(frames.checked_neg()?)
+    }
+
+    /// Add a number of frames to a timecode, stopping at `00:00:00:00` or
+    /// the last frame before 24 hours instead of rolling over.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate25>::new(23, 59, 59, 20).unwrap();
+    /// assert_eq!(tc.saturating_add(100).to_string(), "23:59:59:24");
+    /// assert_eq!(tc.saturating_add(-3000000).to_string(), "00:00:00:00");
+    /// ```
+    pub fn saturating_add(self, frames: i64) -> Self
+    where
+        T: FrameRate,
+    {
+        let frame_number = (self.frame_number as i128 + frames as i128)
+            .clamp(0, T::MAX_FRAMES as i128 - 1);
+
+        Timecode::from(frame_number as u32)
+    }
+
+    /// Remove a number of frames from a timecode, stopping at `00:00:00:00`
+    /// or the last frame before 24 hours instead of rolling over.
+    pub fn saturating_sub(self, frames: i64) -> Self
+    where
+        T: FrameRate,
+    {
+        self.saturating_add(frames.saturating_neg())
+    }
+
+    /// Add a number of frames to a timecode, rolling over at 24 hours. This
+    /// is the same as using the `+` operator.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate25>::new(23, 59, 59, 20).unwrap();
+    /// assert_eq!(tc.wrapping_add(5).to_string(), "00:00:00:00");
+    /// ```
+    pub fn wrapping_add(self, frames: i64) -> Self
+    where
+        T: FrameRate,
+    {
+        self.offset(frames as i128)
+    }
+
+    /// Remove a number of frames from a timecode, rolling over at 24 hours.
+    /// This is the same as using the `-` operator.
+    pub fn wrapping_sub(self, frames: i64) -> Self
+    where
+        T: FrameRate,
+    {
+        self.offset(-(frames as i128))
+    }
+
+    /// Move the timecode by a number of frames, rolling over at 24 hours.
+    fn offset(self, frames: i128) -> Self
+    where
+        T: FrameRate,
+    {
+        let frame_number = (self.frame_number as i128 + frames)
+            .rem_euclid(T::MAX_FRAMES as i128);
+
+        Timecode::from(frame_number as u32)
+    }
 }
 
 /// Parse a string into a timecode.
@@ -152,6 +310,17 @@ impl<T> Timecode<T> {
 /// let tc4 = Timecode::<FrameRate2997>::from_str("00.00.10.00").unwrap();
 /// assert_eq!(tc4.frame_number, 300);
 /// ```
+///
+/// Frame rates above 100 fps take a frame field of up to three digits.
+///
+/// ```
+/// use video_timecode::*;
+/// use std::str::FromStr;
+///
+/// let tc = Timecode::<FrameRate120>::from_str("00:00:10:119").unwrap();
+/// assert_eq!(tc.frame_number, 1319);
+/// assert_eq!(tc.to_string(), "00:00:10:119");
+/// ```
 impl<T> str::FromStr for Timecode<T>
 where
     T: FrameRate,
@@ -169,7 +338,7 @@ where
     ///
     /// // Semicolon notation only allowed for drop frame frame rates.
     /// match Timecode::<FrameRate24>::from_str("00:00:10;00") {
-    ///     Err(TimecodeError { kind: InvalidFormat }) => {}
+    ///     Err(TimecodeError { kind: InvalidFormat, .. }) => {}
     ///     _ => panic!()
     /// }
     /// ```
@@ -184,145 +353,375 @@ where
     ///
     /// // This is a dropped frame.
     /// match Timecode::<FrameRate2997>::from_str("00:01:00;00") {
-    ///     Err(TimecodeError { kind: InvalidTimecode }) => {}
+    ///     Err(TimecodeError { kind: InvalidTimecode, .. }) => {}
     ///     _ => panic!()
     /// }
     /// ```
+    ///
+    /// The `reason` and `position` of the error say what was wrong, and
+    /// where.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::str::FromStr;
+    ///
+    /// let error = Timecode::<FrameRate2997>::from_str("00:01:00;00")
+    ///     .unwrap_err();
+    /// assert_eq!(error.position, Some(9));
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "invalid timecode: frame 0 of minute 1 is dropped in drop frame \
+    ///      timecode, at position 9"
+    /// );
+    /// ```
     type Err = TimecodeError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use self::TimecodeErrorKind::*;
+        let fields = parse_fields(s, T::FPS)?;
 
-        let mut colon_notation = false;
-        let mut semi_colon_notation = false;
-        let mut dot_notation = false;
+        if fields.drop_frame && !T::DROP_FRAME {
+            return Err(fields.drop_frame_notation());
+        }
 
-        let mut it = s.chars();
+        Timecode::<T>::new(
+            fields.hour as u8,
+            fields.minute,
+            fields.second,
+            fields.frame,
+        )
+        .map_err(|error| fields.locate(error))
+    }
+}
 
-        let hour_string: String = it.by_ref().take(2).collect();
-        let hour: u8 = match hour_string.parse() {
-            Ok(n) if n < 60 => n,
-            _ => {
-                return Err(TimecodeError {
-                    kind: InvalidFormat,
-                });
-            }
-        };
+/// The error for timecode fields that are not valid at a frame rate, saying
+/// which field is wrong.
+fn invalid_fields(
+    fps: u32,
+    drop_frame: bool,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+) -> TimecodeError {
+    use self::TimecodeErrorKind::InvalidTimecode;
+    use self::TimecodeField::*;
+
+    let fields = [
+        (Hour, hour as u64, 23),
+        (Minute, minute as u64, 59),
+        (Second, second as u64, 59),
+        (Frame, frame as u64, fps as u64 - 1),
+    ];
+
+    for &(field, value, max) in fields.iter() {
+        if value > max {
+            return TimecodeError::out_of_range(
+                InvalidTimecode,
+                field,
+                value,
+                max,
+            );
+        }
+    }
 
-        let minute_sep_char = it.next();
-        match minute_sep_char {
-            Some(':') => colon_notation = true,
-            Some(';') => semi_colon_notation = true,
-            Some('.') => dot_notation = true,
-            _ => {
-                return Err(TimecodeError {
-                    kind: InvalidFormat,
-                });
-            }
-        };
+    if drop_frame
+        && second == 0
+        && minute % 10 != 0
+        && (frame as u32) < fps / 15
+    {
+        return TimecodeError::dropped_frame(minute, frame);
+    }
 
-        let minute_string: String = it.by_ref().take(2).collect();
-        let minute: u8 = match minute_string.parse() {
-            Ok(n) if n < 60 => n,
-            _ => {
-                return Err(TimecodeError {
-                    kind: InvalidFormat,
-                });
-            }
-        };
+    TimecodeError::from(InvalidTimecode)
+}
+
+/// The fields of a timecode string, and whether it used drop frame
+/// notation.
+struct Fields {
+    hour: u64,
+    minute: u8,
+    second: u8,
+    frame: u8,
+    drop_frame: bool,
+    hour_digits: usize,
+}
 
-        let second_sep_char = it.next();
-        match second_sep_char {
-            Some(':') if colon_notation => {}
-            Some(';') if semi_colon_notation => {}
-            Some('.') if dot_notation => {}
-            _ => {
-                return Err(TimecodeError {
-                    kind: InvalidFormat,
-                });
+impl Fields {
+    /// The position of a field in the string.
+    fn position(&self, field: TimecodeField) -> usize {
+        match field {
+            TimecodeField::Hour | TimecodeField::FrameNumber => 0,
+            TimecodeField::Minute => self.hour_digits + 1,
+            TimecodeField::Second => self.hour_digits + 4,
+            TimecodeField::Frame | TimecodeField::Subframe => {
+                self.hour_digits + 7
             }
         }
+    }
 
-        let second_string: String = it.by_ref().take(2).collect();
-        let second: u8 = match second_string.parse() {
-            Ok(n) if n < 60 => n,
-            _ => {
-                return Err(TimecodeError {
-                    kind: InvalidFormat,
-                });
+    /// Add the position of the field that an error from making a timecode
+    /// out of the fields is about.
+    fn locate(&self, error: TimecodeError) -> TimecodeError {
+        let field = match error.reason {
+            Some(TimecodeErrorReason::OutOfRange { field, .. }) => field,
+            Some(TimecodeErrorReason::DroppedFrame { .. }) => {
+                TimecodeField::Frame
             }
+            _ => return error,
         };
 
-        let frame_sep_char = it.next();
-        let drop_frame = match frame_sep_char {
-            Some(':') if colon_notation => false,
-            Some(';') if semi_colon_notation || colon_notation => true,
-            Some('.') if dot_notation || colon_notation => true,
-            _ => {
-                return Err(TimecodeError {
-                    kind: InvalidFormat,
-                });
-            }
-        };
+        error.at(self.position(field))
+    }
 
-        let frame_string: String = it.by_ref().take(2).collect();
-        let frame: u8 = match frame_string.parse() {
-            Ok(n) => n,
-            _ => {
-                return Err(TimecodeError {
-                    kind: InvalidFormat,
-                });
-            }
-        };
+    /// The error for drop frame notation with a non drop frame rate, at the
+    /// separator before the frame field.
+    fn drop_frame_notation(&self) -> TimecodeError {
+        TimecodeError::drop_frame_notation().at(self.hour_digits + 6)
+    }
+}
 
-        if it.next() != None {
-            return Err(TimecodeError {
-                kind: InvalidFormat,
-            });
-        }
+/// Split a timecode string into its fields, and whether it used drop frame
+/// notation. The frame field may have three digits for frame rates above
+/// 100 fps.
+fn parse_fields(s: &str, fps: u32) -> Result<Fields, TimecodeError> {
+    parse_long_fields(s, fps, 2)
+}
 
-        if drop_frame && !T::DROP_FRAME {
-            return Err(TimecodeError {
-                kind: InvalidFormat,
-            });
-        }
+/// Split a timecode string into its fields like `parse_fields`, allowing an
+/// hour field with up to `max_hour_digits` digits.
+fn parse_long_fields(
+    s: &str,
+    fps: u32,
+    max_hour_digits: usize,
+) -> Result<Fields, TimecodeError> {
+    use self::TimecodeErrorKind::*;
+
+    let hour_digits =
+        s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if hour_digits < 2 {
+        return Err(TimecodeError::unexpected(s, hour_digits, "a digit"));
+    }
+    if hour_digits > max_hour_digits {
+        return Err(TimecodeError::unexpected(
+            s,
+            max_hour_digits,
+            "a separator",
+        ));
+    }
 
-        match Timecode::<T>::new(hour, minute, second, frame) {
-            Ok(timecode) => Ok(timecode),
-            Err(_) => Err(TimecodeError {
-                kind: InvalidTimecode,
-            }),
+    let hour = match s[..hour_digits].parse() {
+        Ok(n) => n,
+        _ => return Err(TimecodeError::from(InvalidFormat).at(0)),
+    };
+
+    let separator = parse_separator(s, hour_digits, &[':', ';', '.'])?;
+
+    let (minute, end) = parse_digits(s, hour_digits + 1, 2, 2)?;
+    if minute > 59 {
+        return Err(TimecodeError::out_of_range(
+            InvalidFormat,
+            TimecodeField::Minute,
+            minute as u64,
+            59,
+        )
+        .at(hour_digits + 1));
+    }
+
+    parse_separator(s, end, &[separator])?;
+
+    let (second, end) = parse_digits(s, end + 1, 2, 2)?;
+    if second > 59 {
+        return Err(TimecodeError::out_of_range(
+            InvalidFormat,
+            TimecodeField::Second,
+            second as u64,
+            59,
+        )
+        .at(hour_digits + 4));
+    }
+
+    let frame_separators: &[char] = match separator {
+        ':' => &[':', ';', '.'],
+        _ => &[separator],
+    };
+    let drop_frame = parse_separator(s, end, frame_separators)? != ':';
+
+    let (frame, end) = parse_digits(s, end + 1, 1, frame_digits(fps))?;
+    if frame > u8::MAX as u32 {
+        return Err(TimecodeError::out_of_range(
+            InvalidTimecode,
+            TimecodeField::Frame,
+            frame as u64,
+            fps as u64 - 1,
+        )
+        .at(hour_digits + 7));
+    }
+
+    if end < s.len() {
+        return Err(TimecodeError::unexpected(s, end, "the end"));
+    }
+
+    Ok(Fields {
+        hour,
+        minute: minute as u8,
+        second: second as u8,
+        frame: frame as u8,
+        drop_frame,
+        hour_digits,
+    })
+}
+
+/// Parse the separator at `position` in `s`, which must be one of `allowed`.
+fn parse_separator(
+    s: &str,
+    position: usize,
+    allowed: &[char],
+) -> Result<char, TimecodeError> {
+    match s[position..].chars().next() {
+        Some(c) if allowed.contains(&c) => Ok(c),
+        _ => {
+            let expected = match *allowed {
+                [':'] => "':'",
+                [';'] => "';'",
+                ['.'] => "'.'",
+                _ => "a separator",
+            };
+
+            Err(TimecodeError::unexpected(s, position, expected))
         }
     }
 }
 
+/// Parse a field of `min_digits` up to `max_digits` digits at `position` in
+/// `s`, returning its value and the position after it.
+fn parse_digits(
+    s: &str,
+    position: usize,
+    min_digits: usize,
+    max_digits: usize,
+) -> Result<(u32, usize), TimecodeError> {
+    let digits = s[position..]
+        .bytes()
+        .take(max_digits)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+
+    if digits < min_digits {
+        return Err(TimecodeError::unexpected(s, position + digits, "a digit"));
+    }
+
+    let end = position + digits;
+    let value = s[position..end]
+        .bytes()
+        .fold(0, |value, b| value * 10 + (b - b'0') as u32);
+
+    Ok((value, end))
+}
+
+/// Format the timecode as `HH:MM:SS:FF`, with a semicolon before the frame
+/// field for drop frame frame rates. The alternate form, `{:#}`, adds the
+/// frame rate, and the width, fill and alignment flags pad the result.
+///
+/// Frame rates above 100 fps have three digits in the frame field, as in
+/// `HH:MM:SS:FFF`, so that all the frames of a second have the same width.
+///
+/// For other layouts, use a [TimecodeFormat](struct.TimecodeFormat.html).
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc = Timecode::<FrameRate2997>::new(10, 0, 0, 0).unwrap();
+/// assert_eq!(tc.to_string(), "10:00:00;00");
+/// assert_eq!(format!("{:#}", tc), "10:00:00;00@29.97DF");
+/// assert_eq!(format!("{:-^15}", tc), "--10:00:00;00--");
+/// ```
 impl<T> fmt::Display for Timecode<T>
 where
     T: FrameRate,
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let separator = match T::DROP_FRAME {
-            true => ';',
-            false => ':',
-        };
+        // Padding and the frame rate need the whole string to be formatted
+        // first.
+        if f.width().is_some() || f.alternate() {
+            return fmt::Display::fmt(&TimecodeFormat::new().display(*self), f);
+        }
 
-        write!(
+        format_fields(
             f,
-            "{:02}:{:02}:{:02}{}{:02}",
-            self.hour, self.minute, self.second, separator, self.frame
+            self.hour as u64,
+            self.minute,
+            self.second,
+            self.frame,
+            T::FPS,
+            T::DROP_FRAME,
         )
     }
 }
 
+/// Write timecode fields, using a semicolon before the frame field for drop
+/// frame timecodes.
+fn format_fields(
+    f: &mut fmt::Formatter,
+    hour: u64,
+    minute: u8,
+    second: u8,
+    frame: u8,
+    fps: u32,
+    drop_frame: bool,
+) -> fmt::Result {
+    let separator = match drop_frame {
+        true => ';',
+        false => ':',
+    };
+
+    write!(
+        f,
+        "{:02}:{:02}:{:02}{}{:0width$}",
+        hour,
+        minute,
+        second,
+        separator,
+        frame,
+        width = frame_digits(fps)
+    )
+}
+
+/// The absolute value of a float. `f64::abs` is only in `core` in much
+/// newer versions of Rust than this crate supports.
+fn abs(x: f64) -> f64 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+/// Number of digits in the frame field for a frame rate.
+fn frame_digits(fps: u32) -> usize {
+    if fps > 100 {
+        3
+    } else {
+        2
+    }
+}
+
 macro_rules! impl_int_all {
     ($($t:ty)*) => ($(
         /// Create a timecode with the the given frame number.
+        ///
+        /// Frame numbers at or after 24 hours roll over.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the frame number is negative. Use a
+        /// [TimecodeDuration](struct.TimecodeDuration.html) for signed
+        /// offsets instead.
         impl<T> From<$t> for Timecode<T>
         where
             T: FrameRate,
         {
             fn from(frame_number: $t) -> Self {
-                let new_frame_number = frame_number.normalize(T::MAX_FRAMES as $t);
+                let new_frame_number = frame_number.normalize(T::MAX_FRAMES);
 
                 let (hour, minute, second, frame) =
                     T::calculate_time_code(new_frame_number);
@@ -346,7 +745,7 @@ macro_rules! impl_int_all {
             type Output = Self;
 
             fn add(self, other: $t) -> Self {
-                Timecode::<T>::from(self.frame_number as $t + other)
+                self.offset(other as i128)
             }
         }
 
@@ -356,17 +755,7 @@ macro_rules! impl_int_all {
             T: FrameRate,
         {
             fn add_assign(&mut self, other: $t) {
-                let new_frame_number = (self.frame_number as $t + other)
-                    .normalize(T::MAX_FRAMES as $t);
-
-                let (hour, minute, second, frame) =
-                    T::calculate_time_code(new_frame_number);
-
-                self.hour = hour;
-                self.minute = minute;
-                self.second = second;
-                self.frame = frame;
-                self.frame_number = new_frame_number;
+                *self = self.offset(other as i128);
             }
         }
 
@@ -378,7 +767,7 @@ macro_rules! impl_int_all {
             type Output = Self;
 
             fn sub(self, other: $t) -> Self {
-                Timecode::<T>::from(self.frame_number as $t - other)
+                self.offset(-(other as i128))
             }
         }
 
@@ -388,17 +777,7 @@ macro_rules! impl_int_all {
             T: FrameRate,
         {
             fn sub_assign(&mut self, other: $t) {
-                let new_frame_number = (self.frame_number as $t - other)
-                    .normalize(T::MAX_FRAMES as $t);
-
-                let (hour, minute, second, frame) =
-                    T::calculate_time_code(new_frame_number);
-
-                self.hour = hour;
-                self.minute = minute;
-                self.second = second;
-                self.frame = frame;
-                self.frame_number = new_frame_number;
+                *self = self.offset(-(other as i128));
             }
         }
     )*)
@@ -0,0 +1,331 @@
+//! Linear timecode (LTC) frames, as defined in SMPTE ST 12-1.
+//!
+//! Each video frame has an 80-bit LTC word, made of 64 bits of data and a
+//! 16-bit sync word. The data is the timecode as BCD digits, a few flags,
+//! and 32 user bits in eight groups of four bits, which can carry things
+//! like a date or a reel number.
+//!
+//! An [LtcFrame](struct.LtcFrame.html) is encoded into ten bytes, with the
+//! bits in the order they are sent: bit 0 of the word is the least
+//! significant bit of the first byte.
+//!
+//! ```
+//! use video_timecode::*;
+//! use video_timecode::ltc::*;
+//!
+//! let mut frame = LtcFrame::new(
+//!     Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap(),
+//! );
+//! frame.user_bits = 0x2026_1016;
+//!
+//! let bytes = frame.encode();
+//! assert_eq!(bytes[8..], [0xfc, 0xbf]);
+//!
+//! let decoded = LtcFrame::<FrameRate25>::decode(&bytes).unwrap();
+//! assert_eq!(decoded, frame);
+//! ```
+//!
+//! The words can also be sent as audio, biphase mark encoded, with an
+//! [LtcGenerator](struct.LtcGenerator.html), and read back with an
+//! [LtcDecoder](struct.LtcDecoder.html). With the `std` feature, the audio
+//! can be read from and written to WAV files with [Wav](struct.Wav.html).
+
+mod decoder;
+mod generator;
+#[cfg(feature = "std")]
+mod wav;
+
+pub use self::decoder::{DecodedFrame, DecodedFrames, Direction, LtcDecoder};
+pub use self::generator::LtcGenerator;
+#[cfg(feature = "std")]
+pub use self::wav::Wav;
+
+use {FrameRate, FrameRate2398, FrameRate24, FrameRate25, FrameRate2997,
+     FrameRate2997Ndf, FrameRate30, Timecode, TimecodeError,
+     TimecodeErrorKind};
+
+/// The sync word at the end of each LTC word, as bits 64 to 79.
+const SYNC_WORD: u16 = 0b1011_1111_1111_1100;
+
+/// Frame rates that LTC can carry. The frame field has room for frames up to
+/// 39, so higher frame rates are carried as
+/// [frame pairs](../struct.FramePair.html) at one of these rates.
+pub trait LtcFrameRate: FrameRate {}
+
+macro_rules! impl_ltc_frame_rate {
+    ($($frame_rate:ident),*) => {
+        $(
+            impl LtcFrameRate for $frame_rate {}
+
+            const _: () = assert!($frame_rate::FPS <= 30);
+        )*
+    };
+}
+
+impl_ltc_frame_rate!(
+    FrameRate2398,
+    FrameRate24,
+    FrameRate25,
+    FrameRate2997,
+    FrameRate2997Ndf,
+    FrameRate30
+);
+
+/// The contents of an LTC word.
+///
+/// The drop frame flag is set from the frame rate, and the polarity
+/// correction bit and sync word are filled in when encoding.
+#[derive(Debug, PartialEq)]
+pub struct LtcFrame<FrameRate> {
+    pub timecode: Timecode<FrameRate>,
+
+    /// The eight groups of user bits, with the first group in the four
+    /// least significant bits.
+    pub user_bits: u32,
+
+    /// The color frame flag, set when the timecode is locked to the color
+    /// framing of the video.
+    pub color_frame: bool,
+
+    /// The binary group flags, which tell what the user bits hold. The
+    /// least significant bit is BGF0, and only the three lowest bits are
+    /// used.
+    pub binary_group_flags: u8,
+}
+
+impl<T> Clone for LtcFrame<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LtcFrame<T> {}
+
+impl<T> From<Timecode<T>> for LtcFrame<T>
+where
+    T: LtcFrameRate,
+{
+    fn from(timecode: Timecode<T>) -> Self {
+        LtcFrame::new(timecode)
+    }
+}
+
+impl<T> LtcFrame<T>
+where
+    T: LtcFrameRate,
+{
+    /// Returns a frame with the given timecode, and no user bits or flags.
+    pub fn new(timecode: Timecode<T>) -> LtcFrame<T> {
+        LtcFrame {
+            timecode,
+            user_bits: 0,
+            color_frame: false,
+            binary_group_flags: 0,
+        }
+    }
+
+    /// Encode the frame as an 80-bit LTC word, with bit 0 of the word as
+    /// the least significant bit of the first byte.
+    ///
+    /// The polarity correction bit is set so that the word has an even
+    /// number of zero bits, which makes every word start at the same
+    /// polarity when it is biphase mark encoded.
+    pub fn encode(&self) -> [u8; 10] {
+        let mut data = pack_data(
+            &self.timecode,
+            self.user_bits,
+            self.color_frame,
+            self.binary_group_flags,
+        );
+
+        // The sync word has three zero bits, so the data needs an odd
+        // number of them.
+        if data.count_zeros() % 2 == 0 {
+            data |= 1 << flag_positions::<T>().polarity;
+        }
+
+        let word = data as u128 | (SYNC_WORD as u128) << 64;
+
+        let mut bytes = [0; 10];
+        bytes.copy_from_slice(&word.to_le_bytes()[..10]);
+        bytes
+    }
+
+    /// Decode an 80-bit LTC word, with bit 0 of the word as the least
+    /// significant bit of the first byte.
+    ///
+    /// If the sync word is missing or a digit is not valid BCD, an error
+    /// with the `TimecodeErrorKind`
+    /// [InvalidFormat](../enum.TimecodeErrorKind.html#variant.InvalidFormat)
+    /// is returned. If the drop frame flag does not match the frame rate,
+    /// it is
+    /// [FrameRateMismatch](../enum.TimecodeErrorKind.html#variant.FrameRateMismatch),
+    /// and if the timecode is not valid for the frame rate, it is
+    /// [InvalidTimecode](../enum.TimecodeErrorKind.html#variant.InvalidTimecode).
+    ///
+    /// The polarity correction bit is not checked.
+    pub fn decode(bytes: &[u8; 10]) -> Result<LtcFrame<T>, TimecodeError> {
+        let mut word = [0; 16];
+        word[..10].copy_from_slice(bytes);
+        let word = u128::from_le_bytes(word);
+
+        if (word >> 64) as u16 != SYNC_WORD {
+            return Err(TimecodeError::from(TimecodeErrorKind::InvalidFormat));
+        }
+
+        let data = unpack_data(word as u64)?;
+
+        Ok(LtcFrame {
+            timecode: data.timecode,
+            user_bits: data.user_bits,
+            color_frame: data.color_frame,
+            binary_group_flags: data.binary_group_flags,
+        })
+    }
+}
+
+/// Positions of the flags that SMPTE ST 12-1 moves around at 25 fps.
+pub(crate) struct FlagPositions {
+    /// BGF0, BGF1 and BGF2.
+    pub binary_group_flags: [u32; 3],
+
+    /// The polarity correction bit in LTC, or the field mark flag in VITC.
+    pub polarity: u32,
+}
+
+pub(crate) fn flag_positions<T>() -> FlagPositions
+where
+    T: FrameRate,
+{
+    if T::FPS == 25 {
+        FlagPositions {
+            binary_group_flags: [27, 58, 43],
+            polarity: 59,
+        }
+    } else {
+        FlagPositions {
+            binary_group_flags: [43, 58, 59],
+            polarity: 27,
+        }
+    }
+}
+
+/// The BCD digits of each field, as the position and width of the units
+/// and the tens.
+const DIGITS: [(u32, u32, u32); 4] = [
+    // Frame
+    (0, 8, 2),
+    // Second
+    (16, 24, 3),
+    // Minute
+    (32, 40, 3),
+    // Hour
+    (48, 56, 2),
+];
+
+const DROP_FRAME_FLAG: u32 = 10;
+const COLOR_FRAME_FLAG: u32 = 11;
+
+/// The data shared by LTC and VITC words, without the polarity correction
+/// or field mark bit.
+pub(crate) struct Data<FrameRate> {
+    pub timecode: Timecode<FrameRate>,
+    pub user_bits: u32,
+    pub color_frame: bool,
+    pub binary_group_flags: u8,
+}
+
+/// Pack the 64 data bits that LTC and VITC words share, leaving the
+/// polarity correction or field mark bit unset.
+pub(crate) fn pack_data<T>(
+    timecode: &Timecode<T>,
+    user_bits: u32,
+    color_frame: bool,
+    binary_group_flags: u8,
+) -> u64
+where
+    T: FrameRate,
+{
+    let mut data = 0;
+
+    let fields = [
+        timecode.frame,
+        timecode.second,
+        timecode.minute,
+        timecode.hour,
+    ];
+
+    for (&(units, tens, _), &value) in DIGITS.iter().zip(fields.iter()) {
+        data |= ((value % 10) as u64) << units;
+        data |= ((value / 10) as u64) << tens;
+    }
+
+    for group in 0..8 {
+        let bits = (user_bits >> (4 * group)) & 0xf;
+
+        data |= (bits as u64) << (4 + 8 * group);
+    }
+
+    data |= (T::DROP_FRAME as u64) << DROP_FRAME_FLAG;
+    data |= (color_frame as u64) << COLOR_FRAME_FLAG;
+
+    let positions = flag_positions::<T>();
+
+    for (i, &position) in positions.binary_group_flags.iter().enumerate() {
+        data |= ((binary_group_flags >> i) as u64 & 1) << position;
+    }
+
+    data
+}
+
+/// Unpack the 64 data bits that LTC and VITC words share.
+pub(crate) fn unpack_data<T>(data: u64) -> Result<Data<T>, TimecodeError>
+where
+    T: FrameRate,
+{
+    let bits = |position: u32, width: u32| {
+        ((data >> position) & ((1 << width) - 1)) as u8
+    };
+
+    let mut fields = [0; 4];
+
+    for (&(units, tens, width), value) in DIGITS.iter().zip(fields.iter_mut())
+    {
+        let digit = bits(units, 4);
+
+        if digit > 9 {
+            return Err(TimecodeError::from(TimecodeErrorKind::InvalidFormat));
+        }
+
+        *value = bits(tens, width) * 10 + digit;
+    }
+
+    if (bits(DROP_FRAME_FLAG, 1) == 1) != T::DROP_FRAME {
+        return Err(TimecodeError::from(
+            TimecodeErrorKind::FrameRateMismatch,
+        ));
+    }
+
+    let [frame, second, minute, hour] = fields;
+    let timecode = Timecode::new(hour, minute, second, frame)?;
+
+    let mut user_bits = 0;
+
+    for group in 0..8 {
+        user_bits |= (bits(4 + 8 * group, 4) as u32) << (4 * group);
+    }
+
+    let positions = flag_positions::<T>();
+    let mut binary_group_flags = 0;
+
+    for (i, &position) in positions.binary_group_flags.iter().enumerate() {
+        binary_group_flags |= bits(position, 1) << i;
+    }
+
+    Ok(Data {
+        timecode,
+        user_bits,
+        color_frame: bits(COLOR_FRAME_FLAG, 1) == 1,
+        binary_group_flags,
+    })
+}
@@ -0,0 +1,296 @@
+use core::marker;
+
+use abs;
+
+use super::{LtcFrame, LtcFrameRate, SYNC_WORD};
+
+/// The direction LTC was read in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Forward,
+
+    /// The audio is played backwards, so the words arrive with their last
+    /// bit first.
+    Reverse,
+}
+
+/// A frame read by an [LtcDecoder](struct.LtcDecoder.html).
+#[derive(Debug, PartialEq)]
+pub struct DecodedFrame<FrameRate> {
+    pub frame: LtcFrame<FrameRate>,
+
+    /// The direction the frame was read in.
+    pub direction: Direction,
+
+    /// The sample where the frame starts, counting from the first sample
+    /// given to the decoder. This is the start of the first bit of the
+    /// word, so when reading in reverse it is the last sample of the frame.
+    pub sample: u64,
+}
+
+impl<T> Clone for DecodedFrame<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DecodedFrame<T> {}
+
+/// Reads LTC from audio, given as PCM samples from -1.0 to 1.0.
+///
+/// The decoder finds the transitions of the biphase mark code where the
+/// signal crosses zero, and follows changes in speed by measuring the
+/// length of the bits as it goes. It starts out expecting the nominal speed
+/// of the frame rate, and can lock on to audio played between about 0.7
+/// and 1.3 times that, in either direction.
+///
+/// A frame is only read if the audio has all of it, including the transition
+/// at its start, so a frame cut off at the start of the audio is missed.
+///
+/// ```
+/// use video_timecode::*;
+/// use video_timecode::ltc::*;
+///
+/// let mut generator = LtcGenerator::new(48000);
+/// let mut decoder = LtcDecoder::<FrameRate25>::new(48000);
+/// let mut samples = [0.0; 1920];
+///
+/// let start = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+/// let mut decoded = Vec::new();
+///
+/// for tc in start.frames().take(3) {
+///     generator.write_frame(&LtcFrame::new(tc), &mut samples);
+///     decoded.extend(decoder.decode(&samples));
+/// }
+/// decoded.extend(decoder.finish());
+///
+/// assert_eq!(decoded.len(), 3);
+/// assert_eq!(decoded[1].frame.timecode.to_string(), "10:00:00:01");
+/// assert_eq!(decoded[1].direction, Direction::Forward);
+/// assert_eq!(decoded[1].sample, 1920);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LtcDecoder<FrameRate> {
+    /// The number of samples read.
+    position: u64,
+    previous: f32,
+    envelope: f32,
+    decay: f32,
+    high: bool,
+
+    /// The last place the signal crossed zero.
+    crossing: f64,
+    last_transition: Option<f64>,
+
+    /// The start of a bit that has had one short half so far.
+    half_bit: Option<f64>,
+    bit_period: f64,
+
+    /// The last 80 bits, with the newest as the most significant bit.
+    word: u128,
+    bits: u64,
+
+    /// Where each of the last 80 bits started, indexed by the bit count.
+    starts: [f64; 80],
+    frame_rate: marker::PhantomData<FrameRate>,
+}
+
+impl<T> LtcDecoder<T>
+where
+    T: LtcFrameRate,
+{
+    /// Returns a decoder for audio at the given sample rate in Hz.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` is zero.
+    pub fn new(sample_rate: u32) -> LtcDecoder<T> {
+        assert!(sample_rate > 0, "Sample rate must be positive.");
+
+        let bit_period = sample_rate as f64 * T::RATE_DENOMINATOR as f64
+            / (T::RATE_NUMERATOR as f64 * 80.0);
+
+        LtcDecoder {
+            position: 0,
+            previous: 0.0,
+            envelope: 0.0,
+            // The level the hysteresis follows falls by about two thirds
+            // in 10 ms.
+            decay: 1.0 - 100.0 / sample_rate as f32,
+            high: false,
+            crossing: 0.0,
+            last_transition: None,
+            half_bit: None,
+            bit_period,
+            word: 0,
+            bits: 0,
+            starts: [0.0; 80],
+            frame_rate: marker::PhantomData,
+        }
+    }
+
+    /// Read a sample, and return a frame if one ended with it.
+    pub fn push(&mut self, sample: f32) -> Option<DecodedFrame<T>> {
+        let position = self.position;
+        let previous = self.previous;
+
+        self.position += 1;
+        self.previous = sample;
+        let level = abs(sample as f64) as f32;
+        self.envelope = level.max(self.envelope * self.decay);
+
+        if (sample >= 0.0) != (previous >= 0.0) {
+            self.crossing = position as f64 - 1.0
+                + (previous / (previous - sample)) as f64;
+        }
+
+        // Only count a transition once the signal is clearly on the other
+        // side, so that noise around zero is ignored.
+        let threshold = (self.envelope * 0.25).max(0.001);
+        let high = if self.high {
+            sample > -threshold
+        } else {
+            sample > threshold
+        };
+
+        if high == self.high {
+            return None;
+        }
+
+        self.high = high;
+        let transition = self.crossing;
+
+        match self.last_transition.replace(transition) {
+            Some(last) => self.interval(last, transition),
+            None => None,
+        }
+    }
+
+    /// Read samples, returning the frames that end in them.
+    pub fn decode<'a>(
+        &'a mut self,
+        samples: &'a [f32],
+    ) -> DecodedFrames<'a, T> {
+        DecodedFrames {
+            decoder: self,
+            samples: samples.iter(),
+        }
+    }
+
+    /// Finish reading at the end of the audio, and return the last frame if
+    /// it only lacks the transition at the start of the next frame.
+    ///
+    /// After this the decoder starts over, like after a gap in the signal.
+    pub fn finish(&mut self) -> Option<DecodedFrame<T>> {
+        let last = self.last_transition.take()?;
+
+        // The signal may have ended right on the last transition, before
+        // getting far enough past zero for it to count.
+        let end = if self.crossing > last {
+            self.crossing
+        } else {
+            self.position as f64
+        };
+
+        let frame = self.interval(last, end);
+
+        self.half_bit = None;
+        self.bits = 0;
+
+        frame
+    }
+
+    /// Handle the time between two transitions, which is either a whole
+    /// zero bit or half of a one bit.
+    fn interval(
+        &mut self,
+        last: f64,
+        transition: f64,
+    ) -> Option<DecodedFrame<T>> {
+        let length = transition - last;
+
+        if length < self.bit_period * 0.75 {
+            self.bit_period = self.bit_period * 0.9 + length * 2.0 * 0.1;
+
+            match self.half_bit.take() {
+                Some(start) => self.bit(true, start, transition),
+                None => {
+                    self.half_bit = Some(last);
+                    None
+                }
+            }
+        } else if length < self.bit_period * 1.5 && self.half_bit.is_none() {
+            self.bit_period = self.bit_period * 0.9 + length * 0.1;
+
+            self.bit(false, last, transition)
+        } else {
+            // A gap in the signal, or a lost half bit. Start over.
+            self.half_bit = None;
+            self.bits = 0;
+
+            None
+        }
+    }
+
+    /// Add a bit, and return a frame if it completes a word.
+    fn bit(
+        &mut self,
+        value: bool,
+        start: f64,
+        end: f64,
+    ) -> Option<DecodedFrame<T>> {
+        self.word = self.word >> 1 | (value as u128) << 79;
+        self.starts[(self.bits % 80) as usize] = start;
+        self.bits += 1;
+
+        if self.bits < 80 {
+            return None;
+        }
+
+        let (word, direction, sample) = if (self.word >> 64) as u16
+            == SYNC_WORD
+        {
+            let oldest = self.starts[(self.bits % 80) as usize];
+
+            (self.word, Direction::Forward, oldest)
+        } else if self.word as u16 == SYNC_WORD.reverse_bits() {
+            (self.word.reverse_bits() >> 48, Direction::Reverse, end)
+        } else {
+            return None;
+        };
+
+        let mut bytes = [0; 10];
+        bytes.copy_from_slice(&word.to_le_bytes()[..10]);
+
+        LtcFrame::decode(&bytes).ok().map(|frame| DecodedFrame {
+            frame,
+            direction,
+            sample: (sample + 0.5).max(0.0) as u64,
+        })
+    }
+}
+
+/// An iterator over the frames in some samples, returned by
+/// [LtcDecoder::decode](struct.LtcDecoder.html#method.decode).
+#[derive(Debug)]
+pub struct DecodedFrames<'a, FrameRate: 'a> {
+    decoder: &'a mut LtcDecoder<FrameRate>,
+    samples: ::core::slice::Iter<'a, f32>,
+}
+
+impl<'a, T> Iterator for DecodedFrames<'a, T>
+where
+    T: LtcFrameRate,
+{
+    type Item = DecodedFrame<T>;
+
+    fn next(&mut self) -> Option<DecodedFrame<T>> {
+        for &sample in self.samples.by_ref() {
+            if let Some(frame) = self.decoder.push(sample) {
+                return Some(frame);
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,215 @@
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+use abs;
+
+use super::{LtcFrame, LtcFrameRate};
+
+/// Generates LTC audio, biphase mark encoded, as PCM samples from -1.0 to
+/// 1.0.
+///
+/// Each frame takes the samples from the start of its frame to the start of
+/// the next, as given by
+/// [Timecode::to_sample](../struct.Timecode.html#method.to_sample), so the
+/// audio of a run of frames lines up with video at the same frame rate.
+///
+/// ```
+/// use video_timecode::*;
+/// use video_timecode::ltc::*;
+///
+/// let tc = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+/// let mut generator = LtcGenerator::new(48000).level(0.25);
+///
+/// let mut samples = [0.0; 1920];
+/// let written = generator.write_frame(&LtcFrame::new(tc), &mut samples);
+/// assert_eq!(written, 1920);
+/// assert!(samples.iter().all(|sample| sample.abs() <= 0.25));
+/// ```
+#[derive(Debug, Clone)]
+pub struct LtcGenerator {
+    sample_rate: u32,
+    level: f32,
+    rise_time: Duration,
+    high: bool,
+}
+
+impl LtcGenerator {
+    /// Returns a generator for the given sample rate in Hz, with a level of
+    /// 0.5 and the rise time of 25 µs that SMPTE ST 12-1 specifies.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` is zero.
+    pub fn new(sample_rate: u32) -> LtcGenerator {
+        assert!(sample_rate > 0, "Sample rate must be positive.");
+
+        LtcGenerator {
+            sample_rate,
+            level: 0.5,
+            rise_time: Duration::from_micros(25),
+            high: false,
+        }
+    }
+
+    /// Set the peak level of the signal, from 0.0 to 1.0.
+    pub fn level(self, level: f32) -> LtcGenerator {
+        LtcGenerator {
+            level: level.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Set the time the signal takes to go from 10% to 90% of a transition.
+    /// The transitions are straight ramps, and a rise time of zero gives a
+    /// square wave.
+    pub fn rise_time(self, rise_time: Duration) -> LtcGenerator {
+        LtcGenerator { rise_time, ..self }
+    }
+
+    /// Write the audio for a frame to the start of `output`, and return the
+    /// number of samples written, which is
+    /// [samples_in_frame](../struct.Timecode.html#method.samples_in_frame)
+    /// for the timecode of the frame.
+    ///
+    /// The signal carries on from the level that the previous frame written
+    /// by this generator ended at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output` is shorter than the frame.
+    pub fn write_frame<T>(
+        &mut self,
+        frame: &LtcFrame<T>,
+        output: &mut [f32],
+    ) -> usize
+    where
+        T: LtcFrameRate,
+    {
+        let timecode = frame.timecode;
+        let length = timecode.samples_in_frame(self.sample_rate) as usize;
+
+        assert!(
+            output.len() >= length,
+            "Output is too short for the frame."
+        );
+
+        // Whether there is a transition at the start of each half bit, and
+        // the level after it. Every bit starts with a transition, and ones
+        // have another in the middle.
+        let bytes = frame.encode();
+        let mut transitions = [false; 160];
+        let mut levels = [false; 160];
+        let mut high = self.high;
+
+        for half_bit in 0..160 {
+            let bit = bytes[half_bit / 16] >> (half_bit / 2 % 8) & 1;
+
+            transitions[half_bit] = half_bit % 2 == 0 || bit == 1;
+
+            if transitions[half_bit] {
+                high = !high;
+            }
+
+            levels[half_bit] = high;
+        }
+
+        // The level in a half bit, counting from the start of the frame.
+        // The next frame starts with a transition.
+        let level_in = |half_bit: i64| {
+            let high = if half_bit < 0 {
+                self.high
+            } else if half_bit >= 160 {
+                !high
+            } else {
+                levels[half_bit as usize]
+            };
+
+            if high {
+                self.level
+            } else {
+                -self.level
+            }
+        };
+
+        let rate = self.sample_rate as f64;
+        let numerator = T::RATE_NUMERATOR as f64;
+        let denominator = T::RATE_DENOMINATOR as f64;
+
+        // A straight ramp from 0% to 100% takes 1.25 times the rise time.
+        // Measured in bits, it can take at most the half bit until the next
+        // transition.
+        let ramp = (self.rise_time.as_secs_f64() * 1.25 * numerator * 80.0
+            / denominator)
+            .min(0.5);
+
+        let start = timecode.to_sample(self.sample_rate) as f64;
+        let frame_start = timecode.frame_number as f64 * denominator * rate;
+
+        for (i, sample) in output[..length].iter_mut().enumerate() {
+            // Position of the sample within the frame, in bits.
+            let position = ((start + i as f64) * numerator - frame_start)
+                * 80.0
+                / (rate * denominator);
+
+            // The half bit the sample is in, and the start of a half bit
+            // nearest to it. The first samples can be a little before the
+            // exact start of the frame, so this rounds down from -1.
+            let half_bit = (position * 2.0 + 1.0) as i64 - 1;
+            let nearest = (position * 2.0 + 1.5) as i64 - 1;
+
+            // The ramps are centered on the transitions, so that the signal
+            // crosses zero at the exact start of each half bit.
+            let offset = position - nearest as f64 / 2.0;
+            let is_transition = nearest == 160
+                || (0..160).contains(&nearest) && transitions[nearest as usize];
+
+            *sample = if is_transition && abs(offset) < ramp / 2.0 {
+                let before = level_in(nearest - 1);
+                let after = level_in(nearest);
+
+                before + (after - before) * (offset / ramp + 0.5) as f32
+            } else {
+                level_in(half_bit)
+            };
+        }
+
+        self.high = high;
+
+        length
+    }
+
+    /// Returns the audio for a run of frames, with the `std` feature.
+    ///
+    /// The frames can be given as [LtcFrame](struct.LtcFrame.html) values,
+    /// or as timecodes, which have no user bits or flags.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use video_timecode::ltc::*;
+    ///
+    /// let start = Timecode::<FrameRate2997>::new(0, 0, 0, 0).unwrap();
+    /// let samples = LtcGenerator::new(48000).generate(start.frames().take(5));
+    /// assert_eq!(samples.len(), 8008);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn generate<I, T>(&mut self, frames: I) -> Vec<f32>
+    where
+        I: IntoIterator,
+        I::Item: Into<LtcFrame<T>>,
+        T: LtcFrameRate,
+    {
+        let mut samples = Vec::new();
+
+        for frame in frames {
+            let frame = frame.into();
+            let start = samples.len();
+            let length = frame.timecode.samples_in_frame(self.sample_rate);
+
+            samples.resize(start + length as usize, 0.0);
+            self.write_frame(&frame, &mut samples[start..]);
+        }
+
+        samples
+    }
+}
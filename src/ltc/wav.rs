@@ -0,0 +1,271 @@
+use core::convert::TryFrom;
+use std::io;
+use std::vec::Vec;
+
+/// PCM audio in a WAV file, with the `std` feature.
+///
+/// This reads and writes enough of the format to get LTC in and out of
+/// other programs: 8, 16, 24 and 32-bit integer samples, and 32 and 64-bit
+/// float samples, are read, and 16-bit integer samples are written.
+///
+/// ```
+/// use video_timecode::*;
+/// use video_timecode::ltc::*;
+///
+/// let start = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+/// let samples = LtcGenerator::new(48000).generate(start.frames().take(25));
+///
+/// let mut file = Vec::new();
+/// Wav::new(48000, 1, samples).write(&mut file).unwrap();
+///
+/// let wav = Wav::read(&file[..]).unwrap();
+/// let samples: Vec<f32> = wav.channel(0).collect();
+///
+/// let mut decoder = LtcDecoder::<FrameRate25>::new(wav.sample_rate);
+/// let mut decoded: Vec<_> = decoder.decode(&samples).collect();
+/// decoded.extend(decoder.finish());
+///
+/// assert_eq!(decoded.len(), 25);
+/// assert_eq!(decoded[24].frame.timecode.to_string(), "10:00:00:24");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Wav {
+    /// The sample rate in Hz.
+    pub sample_rate: u32,
+
+    pub channels: u16,
+
+    /// The samples from -1.0 to 1.0, with the channels interleaved.
+    pub samples: Vec<f32>,
+}
+
+const PCM: u16 = 1;
+const FLOAT: u16 = 3;
+const EXTENSIBLE: u16 = 0xfffe;
+
+impl Wav {
+    /// Returns audio with the given sample rate in Hz, number of channels
+    /// and interleaved samples.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` or `channels` is zero.
+    pub fn new(sample_rate: u32, channels: u16, samples: Vec<f32>) -> Wav {
+        assert!(sample_rate > 0, "Sample rate must be positive.");
+        assert!(channels > 0, "There must be at least one channel.");
+
+        Wav {
+            sample_rate,
+            channels,
+            samples,
+        }
+    }
+
+    /// Read a WAV file.
+    ///
+    /// If the file is not a WAV file with samples in one of the supported
+    /// formats, an error with the `ErrorKind`
+    /// [InvalidData](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidData)
+    /// is returned.
+    pub fn read<R>(mut reader: R) -> io::Result<Wav>
+    where
+        R: io::Read,
+    {
+        let mut file = Vec::new();
+        reader.read_to_end(&mut file)?;
+
+        if file.len() < 12 || &file[0..4] != b"RIFF" || &file[8..12] != b"WAVE"
+        {
+            return Err(invalid_data("not a WAV file"));
+        }
+
+        let mut format = None;
+        let mut rest = &file[12..];
+
+        while rest.len() >= 8 {
+            let id = &rest[0..4];
+            let size = u32_at(rest, 4) as usize;
+            let chunk = &rest[8..];
+            let chunk = &chunk[..size.min(chunk.len())];
+
+            if id == b"fmt " {
+                format = Some(Format::parse(chunk)?);
+            } else if id == b"data" {
+                let format =
+                    format.ok_or_else(|| invalid_data("data before format"))?;
+
+                return Ok(Wav::new(
+                    format.sample_rate,
+                    format.channels,
+                    format.samples(chunk),
+                ));
+            }
+
+            // Chunks are padded to an even size.
+            let next = (8 + size + size % 2).min(rest.len());
+            rest = &rest[next..];
+        }
+
+        Err(invalid_data("no data in WAV file"))
+    }
+
+    /// Write the audio as a WAV file with 16-bit samples. Samples outside
+    /// of -1.0 to 1.0 are clipped.
+    ///
+    /// If the channels, sample rate or samples are too many for the sizes
+    /// in the header, an error with the `ErrorKind`
+    /// [InvalidInput](https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.InvalidInput)
+    /// is returned.
+    pub fn write<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        let block_size = self
+            .channels
+            .checked_mul(2)
+            .ok_or_else(|| invalid_input("too many channels"))?;
+        let byte_rate = self
+            .sample_rate
+            .checked_mul(block_size as u32)
+            .ok_or_else(|| invalid_input("sample rate is too high"))?;
+
+        // The size of the whole file, less eight bytes, has to fit too.
+        let data_size = u32::try_from(self.samples.len())
+            .ok()
+            .and_then(|samples| samples.checked_mul(2))
+            .filter(|&size| size <= u32::MAX - 36)
+            .ok_or_else(|| invalid_input("too many samples"))?;
+
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(36 + data_size).to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&PCM.to_le_bytes());
+        header.extend_from_slice(&self.channels.to_le_bytes());
+        header.extend_from_slice(&self.sample_rate.to_le_bytes());
+        header.extend_from_slice(&byte_rate.to_le_bytes());
+        header.extend_from_slice(&block_size.to_le_bytes());
+        header.extend_from_slice(&16u16.to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&data_size.to_le_bytes());
+        writer.write_all(&header)?;
+
+        let mut data = Vec::with_capacity(data_size as usize);
+
+        for &sample in &self.samples {
+            let sample = (sample.clamp(-1.0, 1.0) * 32767.0).round() as i16;
+
+            data.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        writer.write_all(&data)
+    }
+
+    /// Returns the samples of one channel, counting from zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such channel.
+    pub fn channel(&self, channel: u16) -> impl Iterator<Item = f32> + '_ {
+        assert!(channel < self.channels, "No such channel.");
+
+        self.samples
+            .iter()
+            .skip(channel as usize)
+            .step_by(self.channels as usize)
+            .cloned()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Format {
+    encoding: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits: u16,
+}
+
+impl Format {
+    fn parse(chunk: &[u8]) -> io::Result<Format> {
+        if chunk.len() < 16 {
+            return Err(invalid_data("format chunk is too short"));
+        }
+
+        let mut encoding = u16_at(chunk, 0);
+
+        // The extensible format has the real one at the start of a GUID.
+        if encoding == EXTENSIBLE && chunk.len() >= 26 {
+            encoding = u16_at(chunk, 24);
+        }
+
+        let format = Format {
+            encoding,
+            channels: u16_at(chunk, 2),
+            sample_rate: u32_at(chunk, 4),
+            bits: u16_at(chunk, 14),
+        };
+
+        match (format.encoding, format.bits) {
+            (PCM, 8) | (PCM, 16) | (PCM, 24) | (PCM, 32) => {}
+            (FLOAT, 32) | (FLOAT, 64) => {}
+            _ => return Err(invalid_data("unsupported sample format")),
+        }
+
+        if format.channels == 0 || format.sample_rate == 0 {
+            return Err(invalid_data("no channels or no sample rate"));
+        }
+
+        Ok(format)
+    }
+
+    fn samples(&self, data: &[u8]) -> Vec<f32> {
+        let width = self.bits as usize / 8;
+        let frames = data.len() / (width * self.channels as usize);
+        let data = &data[..frames * width * self.channels as usize];
+
+        data.chunks(width)
+            .map(|bytes| match (self.encoding, width) {
+                (PCM, 1) => (bytes[0] as f32 - 128.0) / 128.0,
+                (PCM, 2) => {
+                    i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32768.0
+                }
+                (PCM, 3) => {
+                    i32::from_le_bytes([0, bytes[0], bytes[1], bytes[2]]) as f32
+                        / 2_147_483_648.0
+                }
+                (PCM, _) => {
+                    i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                        as f32
+                        / 2_147_483_648.0
+                }
+                (_, 4) => {
+                    f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+                }
+                _ => {
+                    let mut double = [0; 8];
+                    double.copy_from_slice(bytes);
+                    f64::from_le_bytes(double) as f32
+                }
+            })
+            .collect()
+    }
+}
+
+fn u16_at(bytes: &[u8], position: usize) -> u16 {
+    u16::from_le_bytes([bytes[position], bytes[position + 1]])
+}
+
+fn u32_at(bytes: &[u8], position: usize) -> u32 {
+    let mut word = [0; 4];
+    word.copy_from_slice(&bytes[position..position + 4]);
+    u32::from_le_bytes(word)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn invalid_input(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, message)
+}
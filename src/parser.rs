@@ -0,0 +1,457 @@
+use {frame_digits, AnyFrameRate, FrameRate, Rounding, Timecode, TimecodeError,
+     TimecodeErrorKind, TimecodeField};
+
+/// A way of writing a timecode, accepted by a
+/// [TimecodeParser](struct.TimecodeParser.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Syntax {
+    /// The strict format accepted by `FromStr` for
+    /// [Timecode](struct.Timecode.html), like `01:00:00:00`.
+    Standard,
+
+    /// Four fields without zero padding, like `1:0:0:0`, with the same
+    /// separators as the standard syntax.
+    Unpadded,
+
+    /// The fields without separators, like `01000000`.
+    Packed,
+
+    /// A frame number, like `+1234` or `1234`.
+    FrameCount,
+
+    /// A real time in hours, minutes, seconds and frames, like `10s`,
+    /// `1h2m` or `1m30.5s12f`. Each unit may only be given once, in that
+    /// order.
+    Units,
+}
+
+impl Syntax {
+    const ALL: [Syntax; 5] = [
+        Syntax::Standard,
+        Syntax::Unpadded,
+        Syntax::Packed,
+        Syntax::FrameCount,
+        Syntax::Units,
+    ];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A timecode returned by
+/// [TimecodeParser::parse](struct.TimecodeParser.html#method.parse), with
+/// the syntax it was written in.
+#[derive(Debug, PartialEq)]
+pub struct ParsedTimecode<FrameRate> {
+    pub timecode: Timecode<FrameRate>,
+
+    /// The syntax that matched the input.
+    pub syntax: Syntax,
+
+    /// The frame rate given after an `@`, if there was one.
+    pub frame_rate: Option<AnyFrameRate>,
+}
+
+/// A parser that accepts timecodes written in several ways, for reading
+/// input from people and from other systems.
+///
+/// The parser is built by choosing the accepted syntaxes, and reports which
+/// one matched. Each syntax may be followed by a frame rate after an `@`,
+/// like `00:00:10:00@25`, which must match the frame rate of the timecode.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let parser = TimecodeParser::new();
+///
+/// let parsed = parser.parse::<FrameRate25>("1:00:00:00").unwrap();
+/// assert_eq!(parsed.timecode.to_string(), "01:00:00:00");
+/// assert_eq!(parsed.syntax, Syntax::Unpadded);
+///
+/// let parsed = parser.parse::<FrameRate25>("+1234").unwrap();
+/// assert_eq!(parsed.timecode.to_string(), "00:00:49:09");
+/// assert_eq!(parsed.syntax, Syntax::FrameCount);
+///
+/// let parsed = parser.parse::<FrameRate25>("1h2m").unwrap();
+/// assert_eq!(parsed.timecode.to_string(), "01:02:00:00");
+///
+/// let parsed = parser.parse::<FrameRate25>("00:00:10:00@25").unwrap();
+/// assert_eq!(parsed.syntax, Syntax::Standard);
+/// assert_eq!(parsed.frame_rate, Some(AnyFrameRate::Fps25));
+///
+/// let parser = TimecodeParser::new().reject(Syntax::FrameCount);
+/// assert!(parser.parse::<FrameRate25>("+1234").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimecodeParser {
+    syntaxes: u8,
+    rate_suffix: bool,
+}
+
+impl TimecodeParser {
+    /// Returns a parser that accepts all syntaxes, and a frame rate suffix.
+    pub fn new() -> TimecodeParser {
+        TimecodeParser {
+            syntaxes: Syntax::ALL.iter().fold(0, |bits, s| bits | s.bit()),
+            rate_suffix: true,
+        }
+    }
+
+    /// Returns a parser that accepts only the strict syntax, like `FromStr`,
+    /// and no frame rate suffix. Other syntaxes can be added with
+    /// [accept](#method.accept).
+    pub fn strict() -> TimecodeParser {
+        TimecodeParser {
+            syntaxes: Syntax::Standard.bit(),
+            rate_suffix: false,
+        }
+    }
+
+    /// Accept a syntax.
+    pub fn accept(self, syntax: Syntax) -> TimecodeParser {
+        TimecodeParser {
+            syntaxes: self.syntaxes | syntax.bit(),
+            ..self
+        }
+    }
+
+    /// Do not accept a syntax.
+    pub fn reject(self, syntax: Syntax) -> TimecodeParser {
+        TimecodeParser {
+            syntaxes: self.syntaxes & !syntax.bit(),
+            ..self
+        }
+    }
+
+    /// Whether to accept a frame rate after an `@`.
+    pub fn rate_suffix(self, accept: bool) -> TimecodeParser {
+        TimecodeParser {
+            rate_suffix: accept,
+            ..self
+        }
+    }
+
+    /// Whether a syntax is accepted.
+    pub fn accepts(&self, syntax: Syntax) -> bool {
+        self.syntaxes & syntax.bit() != 0
+    }
+
+    /// Parse a timecode, trying each accepted syntax in the order they are
+    /// listed in [Syntax](enum.Syntax.html). Whitespace around the input is
+    /// ignored.
+    ///
+    /// If no syntax matches, an error with the `TimecodeErrorKind`
+    /// [InvalidFormat](enum.TimecodeErrorKind.html#variant.InvalidFormat) is
+    /// returned. If a syntax matches but the timecode is not valid for the
+    /// frame rate, it is
+    /// [InvalidTimecode](enum.TimecodeErrorKind.html#variant.InvalidTimecode),
+    /// and if the frame rate suffix does not match, it is
+    /// [FrameRateMismatch](enum.TimecodeErrorKind.html#variant.FrameRateMismatch).
+    pub fn parse<T>(&self, s: &str) -> Result<ParsedTimecode<T>, TimecodeError>
+    where
+        T: FrameRate,
+    {
+        let input = s;
+        let s = s.trim();
+
+        // Positions in errors are counted from the start of the input.
+        let offset = input.len() - input.trim_start().len();
+
+        let (s, frame_rate) = match s.rsplit_once('@') {
+            Some((s, suffix)) if self.rate_suffix => {
+                let frame_rate = parse_rate_suffix::<T>(suffix.trim())
+                    .map_err(|error| error.at(offset + s.len() + 1))?;
+
+                (s.trim_end(), Some(frame_rate))
+            }
+            _ => (s, None),
+        };
+
+        for &syntax in Syntax::ALL.iter() {
+            if !self.accepts(syntax) {
+                continue;
+            }
+
+            let result = match syntax {
+                Syntax::Standard => parse_standard(s),
+                Syntax::Unpadded => parse_unpadded(s),
+                Syntax::Packed => parse_packed(s),
+                Syntax::FrameCount => parse_frame_count(s),
+                Syntax::Units => parse_units(s),
+            };
+
+            if let Some(timecode) = result {
+                return Ok(ParsedTimecode {
+                    timecode: timecode.map_err(|error| error.offset(offset))?,
+                    syntax,
+                    frame_rate,
+                });
+            }
+        }
+
+        Err(invalid_format())
+    }
+}
+
+impl Default for TimecodeParser {
+    fn default() -> TimecodeParser {
+        TimecodeParser::new()
+    }
+}
+
+/// The result of trying one syntax: `None` if the input is not written in
+/// that syntax, or the result of making the timecode if it is.
+type Attempt<T> = Option<Result<Timecode<T>, TimecodeError>>;
+
+fn parse_standard<T>(s: &str) -> Attempt<T>
+where
+    T: FrameRate,
+{
+    match s.parse() {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => None,
+        result => Some(result),
+    }
+}
+
+fn parse_unpadded<T>(s: &str) -> Attempt<T>
+where
+    T: FrameRate,
+{
+    let mut values = [0u32; 4];
+    let mut positions = [0; 4];
+    let mut separators = [':'; 3];
+    let mut rest = s;
+
+    for (i, value) in values.iter_mut().enumerate() {
+        let max_digits = if i == 3 { frame_digits(T::FPS) } else { 2 };
+        let end = rest.find(&[':', ';', '.'][..]).unwrap_or(rest.len());
+
+        positions[i] = s.len() - rest.len();
+        *value = parse_digits(&rest[..end], max_digits)?;
+        rest = &rest[end..];
+
+        if i < 3 {
+            separators[i] = rest.chars().next()?;
+            rest = &rest[1..];
+        }
+    }
+
+    if !rest.is_empty() {
+        return None;
+    }
+
+    // The separators are the same as in the standard syntax: one kind for
+    // all the fields, except that colons may end with a semicolon or a dot.
+    let [first, second, before_frame] = separators;
+    if second != first || (first != ':' && before_frame != first) {
+        return None;
+    }
+
+    if before_frame != ':' && !T::DROP_FRAME {
+        let position = s.rfind(before_frame).unwrap();
+
+        return Some(Err(TimecodeError::drop_frame_notation().at(position)));
+    }
+
+    Some(new_timecode(values, positions))
+}
+
+fn parse_packed<T>(s: &str) -> Attempt<T>
+where
+    T: FrameRate,
+{
+    if s.len() != 6 + frame_digits(T::FPS) {
+        return None;
+    }
+
+    let mut values = [0u32; 4];
+
+    for (i, value) in values.iter_mut().enumerate() {
+        let end = if i == 3 { s.len() } else { 2 * i + 2 };
+
+        *value = parse_digits(s.get(2 * i..end)?, end - 2 * i)?;
+    }
+
+    Some(new_timecode(values, [0, 2, 4, 6]))
+}
+
+fn parse_frame_count<T>(s: &str) -> Attempt<T>
+where
+    T: FrameRate,
+{
+    let digits = s.strip_prefix('+').unwrap_or(s);
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    // Numbers too long for a u64 are out of range all the same.
+    let frame_number = digits.parse::<u64>().unwrap_or(u64::MAX);
+
+    if frame_number >= T::MAX_FRAMES as u64 {
+        let error = frame_number_out_of_range::<T>(frame_number);
+
+        return Some(Err(error.at(s.len() - digits.len())));
+    }
+
+    Some(Ok(Timecode::from(frame_number as u32)))
+}
+
+fn parse_units<T>(s: &str) -> Attempt<T>
+where
+    T: FrameRate,
+{
+    if s.is_empty() {
+        return None;
+    }
+
+    let mut rest = s;
+    let mut nanos: u128 = 0;
+    let mut frames: u64 = 0;
+    let mut units = "hmsf";
+
+    while !rest.is_empty() {
+        let number_length = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let (number, after) = rest.split_at(number_length);
+        let unit = after.chars().next()?;
+
+        // Each unit may only come after the ones before it.
+        let position = units.find(unit)?;
+        units = &units[position + 1..];
+        rest = &after[1..];
+
+        if unit == 's' {
+            nanos += parse_decimal_nanos(number)?;
+        } else {
+            let value = parse_digits(number, 9)? as u128;
+
+            match unit {
+                'h' => nanos += value * 3600 * 1_000_000_000,
+                'm' => nanos += value * 60 * 1_000_000_000,
+                _ => frames = value as u64,
+            }
+        }
+    }
+
+    let frame_number = Rounding::Nearest.divide(
+        nanos as i128 * T::RATE_NUMERATOR as i128,
+        T::RATE_DENOMINATOR as i128 * 1_000_000_000,
+    ) + frames as i128;
+
+    if frame_number >= T::MAX_FRAMES as i128 {
+        let error = frame_number_out_of_range::<T>(frame_number as u64);
+
+        return Some(Err(error.at(0)));
+    }
+
+    Some(Ok(Timecode::from(frame_number as u32)))
+}
+
+/// Parse a number of seconds with up to nine decimals into nanoseconds.
+fn parse_decimal_nanos(s: &str) -> Option<u128> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    if s.ends_with('.') || fraction.len() > 9 {
+        return None;
+    }
+
+    let whole = parse_digits(whole, 9)? as u128;
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        let scale = 10u128.pow(9 - fraction.len() as u32);
+
+        parse_digits(fraction, 9)? as u128 * scale
+    };
+
+    Some(whole * 1_000_000_000 + fraction)
+}
+
+/// Parse a field of one up to `max_digits` ASCII digits.
+fn parse_digits(s: &str, max_digits: usize) -> Option<u32> {
+    if s.is_empty()
+        || s.len() > max_digits
+        || !s.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+/// Make a timecode from hour, minute, second and frame fields that start at
+/// `positions` in the input, failing if they are out of range.
+fn new_timecode<T>(
+    values: [u32; 4],
+    positions: [usize; 4],
+) -> Result<Timecode<T>, TimecodeError>
+where
+    T: FrameRate,
+{
+    use TimecodeField::*;
+
+    let ranges = [(Hour, 23), (Minute, 59), (Second, 59), (Frame, T::FPS - 1)];
+
+    for (i, (&(field, max), &value)) in
+        ranges.iter().zip(values.iter()).enumerate()
+    {
+        if value > max {
+            return Err(TimecodeError::out_of_range(
+                TimecodeErrorKind::InvalidTimecode,
+                field,
+                value as u64,
+                max as u64,
+            )
+            .at(positions[i]));
+        }
+    }
+
+    let [hour, minute, second, frame] = values;
+
+    // With the fields in range, the only error left is a dropped frame.
+    Timecode::new(hour as u8, minute as u8, second as u8, frame as u8)
+        .map_err(|error| error.at(positions[3]))
+}
+
+/// The error for a frame number at or after 24 hours.
+fn frame_number_out_of_range<T>(frame_number: u64) -> TimecodeError
+where
+    T: FrameRate,
+{
+    TimecodeError::out_of_range(
+        TimecodeErrorKind::InvalidTimecode,
+        TimecodeField::FrameNumber,
+        frame_number,
+        T::MAX_FRAMES as u64 - 1,
+    )
+}
+
+/// Parse a frame rate suffix, and check that it matches the frame rate of
+/// the timecode. Without a `DF` or `NDF` suffix, only the rate is checked.
+fn parse_rate_suffix<T>(s: &str) -> Result<AnyFrameRate, TimecodeError>
+where
+    T: FrameRate,
+{
+    let frame_rate: AnyFrameRate = s.parse()?;
+
+    let (numerator, denominator) = frame_rate.rate();
+    let matches = numerator as u64 * T::RATE_DENOMINATOR as u64
+        == T::RATE_NUMERATOR as u64 * denominator as u64
+        && (!s.ends_with("DF") || frame_rate.drop_frame() == T::DROP_FRAME);
+
+    if !matches {
+        return Err(TimecodeError::from(TimecodeErrorKind::FrameRateMismatch));
+    }
+
+    Ok(T::ANY_FRAME_RATE.unwrap_or(frame_rate))
+}
+
+fn invalid_format() -> TimecodeError {
+    TimecodeError::from(TimecodeErrorKind::InvalidFormat)
+}
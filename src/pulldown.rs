@@ -0,0 +1,227 @@
+//! Mapping between 23.976 fps film frames and 29.97 fps video frames through
+//! 2:3 pulldown.
+//!
+//! Pulldown spreads every four film frames, called A, B, C and D, over ten
+//! video fields, which is five video frames. Each video frame is made of a
+//! first and a second field.
+//!
+//! ```
+//! use video_timecode::*;
+//! use video_timecode::pulldown::*;
+//!
+//! let pulldown = Pulldown::new(
+//!     PulldownPattern::Standard,
+//!     Timecode::<FrameRate2398>::new(1, 0, 0, 0).unwrap(),
+//!     Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap(),
+//! );
+//!
+//! let film = Timecode::<FrameRate2398>::new(1, 0, 0, 2).unwrap();
+//! assert_eq!(pulldown.film_cadence(film), CadenceFrame::C);
+//!
+//! let (video, field) = pulldown.film_to_video(film);
+//! assert_eq!(video.to_string(), "01:00:00;02");
+//! assert_eq!(field, Field::Second);
+//! ```
+
+use {FrameRate, FrameRate2398, FrameRate2997, Timecode};
+
+/// The distribution of film frames over video fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PulldownPattern {
+    /// 2:3 pulldown, where the A, B, C and D frames take 2, 3, 2 and 3
+    /// fields. This gives the video frames AA, BB, BC, CD and DD.
+    Standard,
+
+    /// 2:3:3:2 advanced pulldown, where the A, B, C and D frames take 2, 3, 3
+    /// and 2 fields. This gives the video frames AA, BB, BC, CC and DD, so
+    /// that the film frames can be recovered by dropping the BC frame.
+    Advanced,
+}
+
+impl PulldownPattern {
+    /// The first field of each cadence frame, within the ten fields of a
+    /// cadence.
+    fn first_fields(self) -> [i64; 4] {
+        match self {
+            PulldownPattern::Standard => [0, 2, 5, 7],
+            PulldownPattern::Advanced => [0, 2, 5, 8],
+        }
+    }
+
+    /// The cadence frame shown in a field, within the ten fields of a
+    /// cadence.
+    fn cadence_frame(self, field: i64) -> CadenceFrame {
+        let first_fields = self.first_fields();
+        let index = first_fields.iter().rposition(|&f| f <= field).unwrap();
+
+        CadenceFrame::from_index(index)
+    }
+}
+
+/// Position of a film frame within the four frame pulldown cadence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CadenceFrame {
+    A,
+    B,
+    C,
+    D,
+}
+
+impl CadenceFrame {
+    fn from_index(index: usize) -> CadenceFrame {
+        match index {
+            0 => CadenceFrame::A,
+            1 => CadenceFrame::B,
+            2 => CadenceFrame::C,
+            _ => CadenceFrame::D,
+        }
+    }
+}
+
+/// One of the two interlaced fields of a video frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    First,
+    Second,
+}
+
+/// A pulldown cadence, anchored at a film A frame and the video frame where
+/// that A frame starts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pulldown {
+    pattern: PulldownPattern,
+    film_reference: Timecode<FrameRate2398>,
+    video_reference: Timecode<FrameRate2997>,
+}
+
+impl Pulldown {
+    /// Returns a pulldown cadence where the film frame `film_a_frame` is an
+    /// A frame, starting at the first field of the video frame
+    /// `video_a_frame`.
+    pub fn new(
+        pattern: PulldownPattern,
+        film_a_frame: Timecode<FrameRate2398>,
+        video_a_frame: Timecode<FrameRate2997>,
+    ) -> Pulldown {
+        Pulldown {
+            pattern,
+            film_reference: film_a_frame,
+            video_reference: video_a_frame,
+        }
+    }
+
+    /// The pulldown pattern used.
+    pub fn pattern(&self) -> PulldownPattern {
+        self.pattern
+    }
+
+    /// Which cadence frame a film frame is.
+    pub fn film_cadence(&self, film: Timecode<FrameRate2398>) -> CadenceFrame {
+        let offset = offset(film, self.film_reference);
+
+        CadenceFrame::from_index(offset.rem_euclid(4) as usize)
+    }
+
+    /// The video frame and field where a film frame starts.
+    pub fn film_to_video(
+        &self,
+        film: Timecode<FrameRate2398>,
+    ) -> (Timecode<FrameRate2997>, Field) {
+        let offset = offset(film, self.film_reference);
+
+        let field = offset.div_euclid(4) * 10
+            + self.pattern.first_fields()[offset.rem_euclid(4) as usize];
+
+        let video = self.video_reference + field.div_euclid(2);
+        let field = match field.rem_euclid(2) {
+            0 => Field::First,
+            _ => Field::Second,
+        };
+
+        (video, field)
+    }
+
+    /// The number of video fields a film frame is shown in, which is either
+    /// two or three.
+    pub fn film_field_count(&self, film: Timecode<FrameRate2398>) -> u8 {
+        let first_fields = self.pattern.first_fields();
+        let index = self.film_cadence(film) as usize;
+        let next = if index == 3 { 10 } else { first_fields[index + 1] };
+
+        (next - first_fields[index]) as u8
+    }
+
+    /// Which cadence frames the first and second fields of a video frame
+    /// show.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use video_timecode::pulldown::*;
+    ///
+    /// let pulldown = Pulldown::new(
+    ///     PulldownPattern::Standard,
+    ///     Timecode::<FrameRate2398>::new(0, 0, 0, 0).unwrap(),
+    ///     Timecode::<FrameRate2997>::new(0, 0, 0, 0).unwrap(),
+    /// );
+    ///
+    /// let video = Timecode::<FrameRate2997>::new(0, 0, 0, 3).unwrap();
+    /// assert_eq!(
+    ///     pulldown.video_cadence(video),
+    ///     (CadenceFrame::C, CadenceFrame::D)
+    /// );
+    /// ```
+    pub fn video_cadence(
+        &self,
+        video: Timecode<FrameRate2997>,
+    ) -> (CadenceFrame, CadenceFrame) {
+        let field = offset(video, self.video_reference) * 2;
+
+        (
+            self.pattern.cadence_frame(field.rem_euclid(10)),
+            self.pattern.cadence_frame((field + 1).rem_euclid(10)),
+        )
+    }
+
+    /// The film frames shown in the first and second fields of a video
+    /// frame.
+    pub fn video_to_film(
+        &self,
+        video: Timecode<FrameRate2997>,
+    ) -> (Timecode<FrameRate2398>, Timecode<FrameRate2398>) {
+        let field = offset(video, self.video_reference) * 2;
+
+        (self.field_to_film(field), self.field_to_film(field + 1))
+    }
+
+    /// Whether a video frame shows two different film frames, and so has to
+    /// be dropped or reconstructed when removing the pulldown.
+    pub fn is_split_frame(&self, video: Timecode<FrameRate2997>) -> bool {
+        let (first, second) = self.video_cadence(video);
+
+        first != second
+    }
+
+    /// The film frame shown in a field, counted from the first field of the
+    /// reference video frame.
+    fn field_to_film(&self, field: i64) -> Timecode<FrameRate2398> {
+        let cadence = self.pattern.cadence_frame(field.rem_euclid(10));
+
+        self.film_reference + (field.div_euclid(10) * 4 + cadence as i64)
+    }
+}
+
+/// The number of frames from one timecode to another. Timecodes roll over at
+/// midnight, so this is reduced modulo a day to the shorter way around.
+fn offset<T>(timecode: Timecode<T>, reference: Timecode<T>) -> i64
+where
+    T: FrameRate,
+{
+    let day = T::MAX_FRAMES as i64;
+    let offset = (timecode - reference).frames().rem_euclid(day);
+
+    if offset > day / 2 {
+        offset - day
+    } else {
+        offset
+    }
+}
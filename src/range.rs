@@ -0,0 +1,331 @@
+use core::cmp;
+use core::iter;
+
+use {FrameRate, Timecode, TimecodeDuration, TimecodeError, TimecodeErrorKind};
+
+/// A range of frames, from an in point to an out point.
+///
+/// The in point (`start`) is the first frame in the range, and the out
+/// point (`end`) is the first frame after the range, so a range from
+/// `00:00:00:00` to `00:00:01:00` at 25 fps has 25 frames, and does not
+/// contain `00:00:01:00`. A range with the same in and out point is empty.
+///
+/// Since timecodes roll over at 24 hours, a range with an out point before
+/// its in point spans midnight.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc_in = Timecode::<FrameRate25>::new(23, 59, 59, 0).unwrap();
+/// let tc_out = Timecode::<FrameRate25>::new(0, 0, 1, 0).unwrap();
+///
+/// let range = TimecodeRange::new(tc_in, tc_out);
+/// assert_eq!(range.length().frames(), 50);
+/// assert!(range.contains(Timecode::new(0, 0, 0, 0).unwrap()));
+/// assert!(!range.contains(tc_out));
+///
+/// let frames: Vec<String> = range.iter().map(|tc| tc.to_string()).collect();
+/// assert_eq!(frames[24], "23:59:59:24");
+/// assert_eq!(frames[25], "00:00:00:00");
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct TimecodeRange<FrameRate> {
+    start: Timecode<FrameRate>,
+    length: u32,
+}
+
+impl<T> Clone for TimecodeRange<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TimecodeRange<T> {}
+
+impl<T> TimecodeRange<T>
+where
+    T: FrameRate,
+{
+    /// Returns the range from `start` up to, but not including, `end`.
+    pub fn new(start: Timecode<T>, end: Timecode<T>) -> TimecodeRange<T> {
+        TimecodeRange {
+            start,
+            length: (end - start).frames().rem_euclid(T::MAX_FRAMES as i64)
+                as u32,
+        }
+    }
+
+    /// Returns the range starting at `start`, with the given length.
+    ///
+    /// If the length is negative or longer than 24 hours, an error with the
+    /// `TimecodeErrorKind`
+    /// [InvalidTimecode](enum.TimecodeErrorKind.html#variant.InvalidTimecode)
+    /// is returned.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let range = TimecodeRange::with_length(
+    ///     Timecode::<FrameRate24>::new(1, 0, 0, 0).unwrap(),
+    ///     TimecodeDuration::from_frames(48),
+    /// ).unwrap();
+    /// assert_eq!(range.end().to_string(), "01:00:02:00");
+    /// ```
+    pub fn with_length(
+        start: Timecode<T>,
+        length: TimecodeDuration<T>,
+    ) -> Result<TimecodeRange<T>, TimecodeError> {
+        if length.frames() < 0 || length.frames() > T::MAX_FRAMES as i64 {
+            return Err(TimecodeError::from(TimecodeErrorKind::InvalidTimecode));
+        }
+
+        Ok(TimecodeRange {
+            start,
+            length: length.frames() as u32,
+        })
+    }
+
+    /// The first frame in the range.
+    pub fn start(&self) -> Timecode<T> {
+        self.start
+    }
+
+    /// The first frame after the range.
+    pub fn end(&self) -> Timecode<T> {
+        self.start + self.length
+    }
+
+    /// The number of frames in the range.
+    pub fn length(&self) -> TimecodeDuration<T> {
+        TimecodeDuration::from_frames(self.length as i64)
+    }
+
+    /// Whether the range has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Whether a timecode is in the range.
+    pub fn contains(&self, timecode: Timecode<T>) -> bool {
+        self.offset_of(timecode) < self.length as i64
+    }
+
+    /// Whether the two ranges have any frames in common.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = |s| Timecode::<FrameRate25>::new(0, 0, s, 0).unwrap();
+    ///
+    /// let range = TimecodeRange::new(tc(10), tc(20));
+    /// assert!(range.overlaps(&TimecodeRange::new(tc(15), tc(25))));
+    /// assert!(!range.overlaps(&TimecodeRange::new(tc(20), tc(25))));
+    /// ```
+    pub fn overlaps(&self, other: &TimecodeRange<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The frames the two ranges have in common, or `None` if they do not
+    /// overlap.
+    ///
+    /// Two ranges that are together longer than 24 hours can overlap in two
+    /// separate places. In that case the part of the overlap that includes
+    /// the in point of `other` is returned.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = |s| Timecode::<FrameRate25>::new(0, 0, s, 0).unwrap();
+    ///
+    /// let range = TimecodeRange::new(tc(10), tc(20));
+    /// assert_eq!(
+    ///     range.intersection(&TimecodeRange::new(tc(15), tc(25))),
+    ///     Some(TimecodeRange::new(tc(15), tc(20)))
+    /// );
+    /// ```
+    pub fn intersection(
+        &self,
+        other: &TimecodeRange<T>,
+    ) -> Option<TimecodeRange<T>> {
+        self.relative_positions(other)
+            .iter()
+            .filter_map(|&(other_start, other_end)| {
+                let start = cmp::max(0, other_start);
+                let end = cmp::min(self.length as i64, other_end);
+
+                if start < end {
+                    Some(self.sub_range(start, end))
+                } else {
+                    None
+                }
+            })
+            .next()
+    }
+
+    /// The range covering both ranges, if they overlap or are adjacent, or
+    /// `None` if there would be a gap between them.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = |s| Timecode::<FrameRate25>::new(0, 0, s, 0).unwrap();
+    ///
+    /// let range = TimecodeRange::new(tc(10), tc(20));
+    /// assert_eq!(
+    ///     range.union(&TimecodeRange::new(tc(20), tc(25))),
+    ///     Some(TimecodeRange::new(tc(10), tc(25)))
+    /// );
+    /// assert_eq!(range.union(&TimecodeRange::new(tc(21), tc(25))), None);
+    /// ```
+    pub fn union(&self, other: &TimecodeRange<T>) -> Option<TimecodeRange<T>> {
+        self.relative_positions(other)
+            .iter()
+            .filter(|&&(other_start, other_end)| {
+                other_start <= self.length as i64 && other_end >= 0
+            })
+            .map(|&(other_start, other_end)| {
+                let start = cmp::min(0, other_start);
+                let end = cmp::min(
+                    cmp::max(self.length as i64, other_end),
+                    start + T::MAX_FRAMES as i64,
+                );
+
+                self.sub_range(start, end)
+            })
+            .next()
+    }
+
+    /// Split the range into the frames before a timecode, and the frames from
+    /// that timecode on, or `None` if the timecode is not in the range.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = |s| Timecode::<FrameRate25>::new(0, 0, s, 0).unwrap();
+    ///
+    /// let range = TimecodeRange::new(tc(10), tc(20));
+    /// assert_eq!(
+    ///     range.split_at(tc(12)),
+    ///     Some((
+    ///         TimecodeRange::new(tc(10), tc(12)),
+    ///         TimecodeRange::new(tc(12), tc(20))
+    ///     ))
+    /// );
+    /// ```
+    pub fn split_at(
+        &self,
+        timecode: Timecode<T>,
+    ) -> Option<(TimecodeRange<T>, TimecodeRange<T>)> {
+        if !self.contains(timecode) {
+            return None;
+        }
+
+        let offset = self.offset_of(timecode);
+
+        Some((
+            self.sub_range(0, offset),
+            self.sub_range(offset, self.length as i64),
+        ))
+    }
+
+    /// Iterate over the timecodes of each frame in the range.
+    pub fn iter(&self) -> TimecodeRangeIter<T> {
+        TimecodeRangeIter {
+            front: self.start,
+            back: self.end(),
+            remaining: self.length,
+        }
+    }
+
+    /// Number of frames from the in point of the range to a timecode,
+    /// counting forward and rolling over at 24 hours.
+    fn offset_of(&self, timecode: Timecode<T>) -> i64 {
+        (timecode - self.start).frames().rem_euclid(T::MAX_FRAMES as i64)
+    }
+
+    /// The positions of the other range relative to the in point of this
+    /// range, both as it is and moved back by 24 hours.
+    fn relative_positions(
+        &self,
+        other: &TimecodeRange<T>,
+    ) -> [(i64, i64); 2] {
+        let start = self.offset_of(other.start);
+        let end = start + other.length as i64;
+        let day = T::MAX_FRAMES as i64;
+
+        [(start, end), (start - day, end - day)]
+    }
+
+    /// The range between two positions relative to the in point of this
+    /// range.
+    fn sub_range(&self, start: i64, end: i64) -> TimecodeRange<T> {
+        TimecodeRange {
+            start: self.start + start,
+            length: (end - start) as u32,
+        }
+    }
+}
+
+impl<T> IntoIterator for TimecodeRange<T>
+where
+    T: FrameRate,
+{
+    type Item = Timecode<T>;
+    type IntoIter = TimecodeRangeIter<T>;
+
+    fn into_iter(self) -> TimecodeRangeIter<T> {
+        self.iter()
+    }
+}
+
+/// Iterator over the timecodes in a
+/// [TimecodeRange](struct.TimecodeRange.html).
+#[derive(Debug)]
+pub struct TimecodeRangeIter<T> {
+    front: Timecode<T>,
+    back: Timecode<T>,
+    remaining: u32,
+}
+
+impl<T> Iterator for TimecodeRangeIter<T>
+where
+    T: FrameRate,
+{
+    type Item = Timecode<T>;
+
+    fn next(&mut self) -> Option<Timecode<T>> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let timecode = self.front;
+        self.front.increment();
+        self.remaining -= 1;
+
+        Some(timecode)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl<T> DoubleEndedIterator for TimecodeRangeIter<T>
+where
+    T: FrameRate,
+{
+    fn next_back(&mut self) -> Option<Timecode<T>> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.back.decrement();
+        self.remaining -= 1;
+
+        Some(self.back)
+    }
+}
+
+impl<T> ExactSizeIterator for TimecodeRangeIter<T> where T: FrameRate {}
+
+impl<T> iter::FusedIterator for TimecodeRangeIter<T> where T: FrameRate {}
@@ -0,0 +1,171 @@
+use core::time::Duration;
+
+use {FrameRate, Timecode};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// The largest number of nanoseconds that can be multiplied by any exact
+/// frame rate numerator.
+const MAX_NANOS: i128 = i128::MAX / u32::MAX as i128;
+
+/// How to round a real time that falls between two frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round to the frame at or before the given time.
+    Floor,
+
+    /// Round to the closest frame. A time exactly halfway between two frames
+    /// is rounded to the later frame.
+    Nearest,
+
+    /// Round to the frame at or after the given time.
+    Ceil,
+}
+
+impl Rounding {
+    /// Divide, rounding the quotient in this direction. The divisor must be
+    /// positive.
+    pub(crate) fn divide(self, dividend: i128, divisor: i128) -> i128 {
+        match self {
+            Rounding::Floor => dividend.div_euclid(divisor),
+            Rounding::Nearest => {
+                (2 * dividend + divisor).div_euclid(2 * divisor)
+            }
+            Rounding::Ceil => -(-dividend).div_euclid(divisor),
+        }
+    }
+}
+
+impl<T> Timecode<T>
+where
+    T: FrameRate,
+{
+    /// Real time elapsed from `00:00:00:00` to this timecode, using the exact
+    /// frame rate, rounded to the nearest nanosecond.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::time::Duration;
+    ///
+    /// let tc = Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap();
+    /// assert_eq!(tc.to_duration(), Duration::from_micros(3_599_996_400));
+    /// ```
+    pub fn to_duration(&self) -> Duration {
+        T::frames_to_duration(self.frame_number as u64)
+    }
+
+    /// Real time elapsed from `00:00:00:00` to this timecode, in seconds.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate2398>::new(0, 0, 1, 0).unwrap();
+    /// assert_eq!(tc.to_seconds(), 1.001);
+    /// ```
+    pub fn to_seconds(&self) -> f64 {
+        T::frames_to_seconds(self.frame_number as u64)
+    }
+
+    /// Returns the timecode of the frame shown at the given real time after
+    /// `00:00:00:00`, rounded to a frame boundary as specified.
+    ///
+    /// Like when creating a timecode from a frame number, the timecode rolls
+    /// over at 24 hours.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::time::Duration;
+    ///
+    /// let duration = Duration::from_micros(3_599_996_400);
+    /// let tc = Timecode::<FrameRate2997>::from_duration(
+    ///     duration,
+    ///     Rounding::Nearest,
+    /// );
+    /// assert_eq!(tc, Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap());
+    ///
+    /// // 10 ms is a quarter of a frame at 25 fps.
+    /// let duration = Duration::from_millis(10);
+    /// let floor = Timecode::<FrameRate25>::from_duration(
+    ///     duration,
+    ///     Rounding::Floor,
+    /// );
+    /// let ceil = Timecode::<FrameRate25>::from_duration(
+    ///     duration,
+    ///     Rounding::Ceil,
+    /// );
+    /// assert_eq!(floor.frame_number, 0);
+    /// assert_eq!(ceil.frame_number, 1);
+    /// ```
+    pub fn from_duration(duration: Duration, rounding: Rounding) -> Self {
+        Timecode::from_nanos(duration.as_nanos() as i128, rounding)
+    }
+
+    /// Returns the timecode of the frame shown at the given number of
+    /// seconds after `00:00:00:00`, rounded to a frame boundary as
+    /// specified.
+    ///
+    /// The time is first rounded to the nearest nanosecond, so that values
+    /// such as those returned by
+    /// [to_seconds](struct.Timecode.html#method.to_seconds) map back to the
+    /// same timecode with `Rounding::Nearest`.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate2997>::from_seconds(
+    ///     3599.9964,
+    ///     Rounding::Floor,
+    /// );
+    /// assert_eq!(tc.to_string(), "01:00:00;00");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seconds` is negative or not finite, or is more than about
+    /// 10^19.
+    pub fn from_seconds(seconds: f64, rounding: Rounding) -> Self {
+        assert!(seconds.is_finite(), "Time in seconds must be finite.");
+        assert!(seconds >= 0.0, "Time can not be negative.");
+
+        let nanos = seconds * NANOS_PER_SECOND as f64;
+
+        // Converting to an integer saturates, so the range is checked first.
+        assert!(nanos <= MAX_NANOS as f64, "Time is out of range.");
+
+        // Round half up, like `f64::round`, which needs `std`.
+        let truncated = nanos as i128;
+        let nanos = if nanos - truncated as f64 >= 0.5 {
+            truncated + 1
+        } else {
+            truncated
+        };
+
+        Timecode::from_nanos(nanos, rounding)
+    }
+
+    /// Returns the timecode of the frame shown at the given number of
+    /// milliseconds after `00:00:00:00`, rounded to a frame boundary as
+    /// specified.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = Timecode::<FrameRate2398>::from_millis(1001, Rounding::Floor);
+    /// assert_eq!(tc.to_string(), "00:00:01:00");
+    /// ```
+    pub fn from_millis(millis: u64, rounding: Rounding) -> Self {
+        Timecode::from_nanos(millis as i128 * 1_000_000, rounding)
+    }
+
+    fn from_nanos(nanos: i128, rounding: Rounding) -> Self {
+        let frames = nanos
+            .checked_mul(T::RATE_NUMERATOR as i128)
+            .expect("Time is out of range.");
+        let frame_number = rounding.divide(
+            frames,
+            T::RATE_DENOMINATOR as i128 * NANOS_PER_SECOND,
+        );
+
+        Timecode::from(frame_number.rem_euclid(T::MAX_FRAMES as i128) as u32)
+    }
+}
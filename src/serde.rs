@@ -0,0 +1,218 @@
+//! Serialization of timecodes with [Serde](https://serde.rs), with the
+//! `serde` feature.
+//!
+//! By default a [Timecode](../struct.Timecode.html) is serialized as its
+//! `Display` string, like `"01:00:00;00"`, and deserialized with `FromStr`.
+//! The modules here serialize it in other ways, for use with
+//! `#[serde(with = "...")]`:
+//!
+//! * [string](string/index.html): the `Display` string, like the default.
+//! * [frame_number](frame_number/index.html): the frame number, like
+//!   `107892`.
+//! * [fields](fields/index.html): a struct with `hour`, `minute`, `second`
+//!   and `frame` fields.
+//!
+//! Deserialized timecodes are checked like when they are created with
+//! [Timecode::new](../struct.Timecode.html#method.new) or parsed, and the
+//! [TimecodeError](../struct.TimecodeError.html) is passed on as the error
+//! message.
+//!
+//! ```
+//! extern crate serde;
+//! extern crate serde_json;
+//! extern crate video_timecode;
+//!
+//! use serde::{Deserialize, Serialize};
+//! use video_timecode::*;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Clip {
+//!     start: Timecode<FrameRate2997>,
+//!
+//!     #[serde(with = "video_timecode::serde::frame_number")]
+//!     end: Timecode<FrameRate2997>,
+//! }
+//!
+//! # fn main() {
+//! let clip: Clip =
+//!     serde_json::from_str(r#"{"start": "01:00:00;00", "end": 107922}"#)
+//!         .unwrap();
+//! assert_eq!(clip.end.to_string(), "01:00:01;00");
+//!
+//! let json = serde_json::to_string(&clip).unwrap();
+//! assert_eq!(json, r#"{"start":"01:00:00;00","end":107922}"#);
+//! # }
+//! ```
+
+use core::fmt;
+use core::marker;
+
+use serde_crate::de::{self, Deserializer, Visitor};
+use serde_crate::ser::Serializer;
+use serde_crate::{Deserialize, Serialize};
+
+use {FrameRate, Timecode};
+
+/// Serialize the timecode as its `Display` string.
+impl<T> Serialize for Timecode<T>
+where
+    T: FrameRate,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        string::serialize(self, serializer)
+    }
+}
+
+/// Deserialize the timecode from a string, with `FromStr`.
+impl<'de, T> Deserialize<'de> for Timecode<T>
+where
+    T: FrameRate,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        string::deserialize(deserializer)
+    }
+}
+
+/// Serialize a timecode as its `Display` string, like `"01:00:00;00"`, and
+/// deserialize it with `FromStr`.
+pub mod string {
+    use super::*;
+
+    pub fn serialize<T, S>(
+        timecode: &Timecode<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: FrameRate,
+        S: Serializer,
+    {
+        serializer.collect_str(timecode)
+    }
+
+    pub fn deserialize<'de, T, D>(
+        deserializer: D,
+    ) -> Result<Timecode<T>, D::Error>
+    where
+        T: FrameRate,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StringVisitor(marker::PhantomData))
+    }
+
+    struct StringVisitor<T>(marker::PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for StringVisitor<T>
+    where
+        T: FrameRate,
+    {
+        type Value = Timecode<T>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a timecode string like 01:00:00:00")
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Timecode<T>, E>
+        where
+            E: de::Error,
+        {
+            s.parse().map_err(E::custom)
+        }
+    }
+}
+
+/// Serialize a timecode as its frame number, like `107892`.
+///
+/// When deserializing, the frame number must be within the 24 hours of
+/// timecode, and does not roll over like when converting from an integer.
+pub mod frame_number {
+    use super::*;
+
+    pub fn serialize<T, S>(
+        timecode: &Timecode<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: FrameRate,
+        S: Serializer,
+    {
+        serializer.serialize_u32(timecode.frame_number)
+    }
+
+    pub fn deserialize<'de, T, D>(
+        deserializer: D,
+    ) -> Result<Timecode<T>, D::Error>
+    where
+        T: FrameRate,
+        D: Deserializer<'de>,
+    {
+        let frame_number = u32::deserialize(deserializer)?;
+
+        if frame_number >= T::MAX_FRAMES {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(frame_number as u64),
+                &FrameNumberBelow(T::MAX_FRAMES),
+            ));
+        }
+
+        Ok(Timecode::from(frame_number))
+    }
+
+    struct FrameNumberBelow(u32);
+
+    impl de::Expected for FrameNumberBelow {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a frame number below {}", self.0)
+        }
+    }
+}
+
+/// Serialize a timecode as a struct with `hour`, `minute`, `second` and
+/// `frame` fields, like `{"hour": 1, "minute": 0, "second": 0, "frame": 0}`.
+pub mod fields {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(crate = "serde_crate", rename = "Timecode", deny_unknown_fields)]
+    struct Fields {
+        hour: u8,
+        minute: u8,
+        second: u8,
+        frame: u8,
+    }
+
+    pub fn serialize<T, S>(
+        timecode: &Timecode<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        T: FrameRate,
+        S: Serializer,
+    {
+        Fields {
+            hour: timecode.hour,
+            minute: timecode.minute,
+            second: timecode.second,
+            frame: timecode.frame,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(
+        deserializer: D,
+    ) -> Result<Timecode<T>, D::Error>
+    where
+        T: FrameRate,
+        D: Deserializer<'de>,
+    {
+        let fields = Fields::deserialize(deserializer)?;
+
+        Timecode::new(fields.hour, fields.minute, fields.second, fields.frame)
+            .map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,362 @@
+use core::fmt;
+use core::ops;
+use core::time::Duration;
+
+use {FrameRate, Rounding, Timecode, TimecodeDuration, TimecodeError,
+     TimecodeErrorKind, TimecodeField};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// A timecode with a position within the frame, for addressing audio more
+/// finely than a frame.
+///
+/// Each frame is split into a number of subframes, usually 80 or 100, which
+/// is chosen when the value is created. The subframe is written after the
+/// frame field, like `01:00:00:00.45`.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc = SubframeTimecode::<FrameRate25>::parse(80, "01:00:00:24.70")
+///     .unwrap();
+/// assert_eq!(tc.timecode.to_string(), "01:00:00:24");
+/// assert_eq!(tc.subframe, 70);
+///
+/// let tc = tc.wrapping_add_subframes(15);
+/// assert_eq!(tc.to_string(), "01:00:01:00.05");
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct SubframeTimecode<FrameRate> {
+    pub timecode: Timecode<FrameRate>,
+    pub subframe: u8,
+    subframes_per_frame: u8,
+}
+
+impl<T> Clone for SubframeTimecode<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SubframeTimecode<T> {}
+
+impl<T> SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    /// Returns a timecode at the given subframe of a frame, where each frame
+    /// has `subframes_per_frame` subframes.
+    ///
+    /// If `subframes_per_frame` is zero or `subframe` is not less than it,
+    /// an error with the `TimecodeErrorKind`
+    /// [InvalidTimecode](enum.TimecodeErrorKind.html#variant.InvalidTimecode)
+    /// is returned.
+    pub fn new(
+        timecode: Timecode<T>,
+        subframe: u8,
+        subframes_per_frame: u8,
+    ) -> Result<SubframeTimecode<T>, TimecodeError> {
+        if subframe >= subframes_per_frame {
+            return Err(TimecodeError::out_of_range(
+                TimecodeErrorKind::InvalidTimecode,
+                TimecodeField::Subframe,
+                subframe as u64,
+                (subframes_per_frame as u64).saturating_sub(1),
+            ));
+        }
+
+        Ok(SubframeTimecode {
+            timecode,
+            subframe,
+            subframes_per_frame,
+        })
+    }
+
+    /// Parse a timecode string with a subframe field, where each frame has
+    /// `subframes_per_frame` subframes. The timecode takes the same formats
+    /// as for [Timecode](struct.Timecode.html), and is followed by a `.` and
+    /// the subframe, like `01:00:00:00.45`. Without a subframe field, the
+    /// subframe is zero.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let tc = SubframeTimecode::<FrameRate2997>::parse(100, "00:00:10;00.5")
+    ///     .unwrap();
+    /// assert_eq!(tc.subframe, 5);
+    ///
+    /// let tc = SubframeTimecode::<FrameRate2997>::parse(100, "00:00:10.00")
+    ///     .unwrap();
+    /// assert_eq!(tc.subframe, 0);
+    /// ```
+    pub fn parse(
+        subframes_per_frame: u8,
+        s: &str,
+    ) -> Result<SubframeTimecode<T>, TimecodeError> {
+        let (timecode, subframe) = match s.rsplit_once('.') {
+            Some((timecode, subframe)) if field_count(timecode) == 4 => {
+                let position = timecode.len() + 1;
+                let digits =
+                    subframe.bytes().take_while(u8::is_ascii_digit).count();
+
+                if digits == 0 || digits > 3 || digits < subframe.len() {
+                    return Err(TimecodeError::unexpected(
+                        s,
+                        position + digits.min(3),
+                        if digits < 3 { "a digit" } else { "the end" },
+                    ));
+                }
+
+                let subframe = subframe
+                    .bytes()
+                    .fold(0, |value, b| value * 10 + (b - b'0') as u16);
+
+                if subframe >= subframes_per_frame as u16 {
+                    return Err(TimecodeError::out_of_range(
+                        TimecodeErrorKind::InvalidTimecode,
+                        TimecodeField::Subframe,
+                        subframe as u64,
+                        (subframes_per_frame as u64).saturating_sub(1),
+                    )
+                    .at(position));
+                }
+
+                (timecode, subframe as u8)
+            }
+            _ => (s, 0),
+        };
+
+        SubframeTimecode::new(timecode.parse()?, subframe, subframes_per_frame)
+    }
+
+    /// The number of subframes in each frame.
+    pub fn subframes_per_frame(&self) -> u8 {
+        self.subframes_per_frame
+    }
+
+    /// The number of subframes since `00:00:00:00`.
+    pub fn total_subframes(&self) -> u64 {
+        self.timecode.frame_number as u64 * self.subframes_per_frame as u64
+            + self.subframe as u64
+    }
+
+    /// Add a number of subframes, carrying whole frames into the timecode,
+    /// and rolling over at 24 hours.
+    pub fn wrapping_add_subframes(self, subframes: i64) -> Self {
+        let total = self.total_subframes() as i128 + subframes as i128;
+
+        self.with_total_subframes(total)
+    }
+
+    /// Remove a number of subframes, borrowing whole frames from the
+    /// timecode, and rolling over at 24 hours.
+    pub fn wrapping_sub_subframes(self, subframes: i64) -> Self {
+        let total = self.total_subframes() as i128 - subframes as i128;
+
+        self.with_total_subframes(total)
+    }
+
+    /// Real time elapsed from `00:00:00:00` to this position, using the
+    /// exact frame rate, rounded to the nearest nanosecond.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::time::Duration;
+    ///
+    /// let tc = SubframeTimecode::<FrameRate25>::parse(100, "00:00:01:00.50")
+    ///     .unwrap();
+    /// assert_eq!(tc.to_duration(), Duration::from_millis(1020));
+    /// ```
+    pub fn to_duration(&self) -> Duration {
+        let nanos = Rounding::Nearest.divide(
+            self.total_subframes() as i128
+                * T::RATE_DENOMINATOR as i128
+                * NANOS_PER_SECOND,
+            self.subframes_per_frame as i128 * T::RATE_NUMERATOR as i128,
+        );
+
+        Duration::new(
+            (nanos / NANOS_PER_SECOND) as u64,
+            (nanos % NANOS_PER_SECOND) as u32,
+        )
+    }
+
+    /// Real time elapsed from `00:00:00:00` to this position, in seconds.
+    pub fn to_seconds(&self) -> f64 {
+        self.total_subframes() as f64 * T::RATE_DENOMINATOR as f64
+            / (self.subframes_per_frame as f64 * T::RATE_NUMERATOR as f64)
+    }
+
+    /// Returns the subframe at the given real time after `00:00:00:00`,
+    /// rounded to a subframe boundary as specified. Like when creating a
+    /// timecode from a frame number, the timecode rolls over at 24 hours.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    /// use std::time::Duration;
+    ///
+    /// let tc = SubframeTimecode::<FrameRate2997>::from_duration(
+    ///     Duration::from_millis(1000),
+    ///     80,
+    ///     Rounding::Nearest,
+    /// );
+    /// assert_eq!(tc.to_string(), "00:00:00;29.78");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `subframes_per_frame` is zero.
+    pub fn from_duration(
+        duration: Duration,
+        subframes_per_frame: u8,
+        rounding: Rounding,
+    ) -> Self {
+        assert!(
+            subframes_per_frame > 0,
+            "There must be at least one subframe per frame."
+        );
+
+        let subframes = rounding.divide(
+            duration.as_nanos() as i128
+                * subframes_per_frame as i128
+                * T::RATE_NUMERATOR as i128,
+            T::RATE_DENOMINATOR as i128 * NANOS_PER_SECOND,
+        );
+
+        SubframeTimecode {
+            timecode: Timecode::from(0u32),
+            subframe: 0,
+            subframes_per_frame,
+        }
+        .with_total_subframes(subframes)
+    }
+
+    /// The position at a number of subframes since `00:00:00:00`, with the
+    /// same number of subframes per frame, rolling over at 24 hours.
+    fn with_total_subframes(self, subframes: i128) -> Self {
+        let per_frame = self.subframes_per_frame as i128;
+        let subframes =
+            subframes.rem_euclid(T::MAX_FRAMES as i128 * per_frame);
+
+        SubframeTimecode {
+            timecode: Timecode::from((subframes / per_frame) as u32),
+            subframe: (subframes % per_frame) as u8,
+            subframes_per_frame: self.subframes_per_frame,
+        }
+    }
+}
+
+/// The number of fields in a timecode string, counting the separators.
+fn field_count(s: &str) -> usize {
+    s.chars().filter(|&c| c == ':' || c == ';' || c == '.').count() + 1
+}
+
+/// Format the timecode like a [Timecode](struct.Timecode.html), followed by
+/// a `.` and the subframe, like `01:00:00:00.45`.
+impl<T> fmt::Display for SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = if self.subframes_per_frame > 100 { 3 } else { 2 };
+
+        write!(
+            f,
+            "{}.{:0width$}",
+            self.timecode,
+            self.subframe,
+            width = width
+        )
+    }
+}
+
+/// Make a new position by adding two positions together, carrying whole
+/// frames from the subframes into the timecode.
+///
+/// # Panics
+///
+/// Panics if the positions have different numbers of subframes per frame.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc1 = SubframeTimecode::<FrameRate24>::parse(80, "00:00:10:05.60")
+///     .unwrap();
+/// let tc2 = SubframeTimecode::<FrameRate24>::parse(80, "00:00:00:01.30")
+///     .unwrap();
+/// assert_eq!((tc1 + tc2).to_string(), "00:00:10:07.10");
+/// ```
+impl<T> ops::Add for SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    type Output = SubframeTimecode<T>;
+
+    fn add(self, other: Self) -> Self {
+        assert_eq!(
+            self.subframes_per_frame, other.subframes_per_frame,
+            "Can not add timecodes with different subframes per frame."
+        );
+
+        self.wrapping_add_subframes(other.total_subframes() as i64)
+    }
+}
+
+impl<T> ops::AddAssign for SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+/// Move a position forward by a number of whole frames, keeping the
+/// subframe. Like a timecode, the position rolls over at 24 hours.
+impl<T> ops::Add<TimecodeDuration<T>> for SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    type Output = SubframeTimecode<T>;
+
+    fn add(self, other: TimecodeDuration<T>) -> Self {
+        SubframeTimecode {
+            timecode: self.timecode + other,
+            ..self
+        }
+    }
+}
+
+impl<T> ops::AddAssign<TimecodeDuration<T>> for SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    fn add_assign(&mut self, other: TimecodeDuration<T>) {
+        self.timecode += other;
+    }
+}
+
+/// Move a position backward by a number of whole frames, keeping the
+/// subframe. Like a timecode, the position rolls over at 24 hours.
+impl<T> ops::Sub<TimecodeDuration<T>> for SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    type Output = SubframeTimecode<T>;
+
+    fn sub(self, other: TimecodeDuration<T>) -> Self {
+        SubframeTimecode {
+            timecode: self.timecode - other,
+            ..self
+        }
+    }
+}
+
+impl<T> ops::SubAssign<TimecodeDuration<T>> for SubframeTimecode<T>
+where
+    T: FrameRate,
+{
+    fn sub_assign(&mut self, other: TimecodeDuration<T>) {
+        self.timecode -= other;
+    }
+}
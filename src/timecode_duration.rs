@@ -0,0 +1,404 @@
+use core::cmp;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash;
+use core::marker;
+use core::ops;
+use core::str;
+
+use {frame_digits, ExtendedTimecode, FrameRate, Timecode, TimecodeError,
+     TimecodeErrorKind};
+
+/// A signed length of time, counted in frames, generic over types
+/// implementing the trait [FrameRate](trait.FrameRate.html).
+///
+/// Unlike a [Timecode](struct.Timecode.html), which is a position that rolls
+/// over at 24 hours, a duration may be negative and longer than 24 hours.
+///
+/// Subtracting one timecode from another gives the duration between them,
+/// and adding or subtracting a duration to a timecode gives a new timecode.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let tc_in = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+/// let tc_out = Timecode::<FrameRate25>::new(10, 1, 30, 12).unwrap();
+///
+/// let length = tc_out - tc_in;
+/// assert_eq!(length.frames(), 2262);
+/// assert_eq!(length.to_string(), "00:01:30:12");
+/// assert_eq!((tc_in - tc_out).to_string(), "-00:01:30:12");
+///
+/// assert_eq!(tc_in + length, tc_out);
+/// assert_eq!(tc_in + length * 2 - length, tc_out);
+/// ```
+pub struct TimecodeDuration<FrameRate> {
+    frames: i64,
+    frame_rate: marker::PhantomData<FrameRate>,
+}
+
+impl<T> TimecodeDuration<T> {
+    /// Returns a duration of the given number of frames.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let duration = TimecodeDuration::<FrameRate24>::from_frames(48);
+    /// assert_eq!(duration.to_string(), "00:00:02:00");
+    /// ```
+    pub fn from_frames(frames: i64) -> TimecodeDuration<T> {
+        TimecodeDuration {
+            frames,
+            frame_rate: marker::PhantomData,
+        }
+    }
+
+    /// The number of frames in this duration.
+    pub fn frames(&self) -> i64 {
+        self.frames
+    }
+
+    /// Add two durations, returning `None` on overflow.
+    ///
+    /// ```
+    /// use video_timecode::*;
+    ///
+    /// let max = TimecodeDuration::<FrameRate25>::from_frames(i64::MAX);
+    /// let one = TimecodeDuration::<FrameRate25>::from_frames(1);
+    /// assert_eq!(max.checked_add(one), None);
+    /// assert_eq!(max.saturating_add(one), max);
+    /// assert_eq!(max.wrapping_add(one).frames(), i64::MIN);
+    /// ```
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.frames.checked_add(other.frames).map(Self::from_frames)
+    }
+
+    /// Subtract a duration, returning `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.frames.checked_sub(other.frames).map(Self::from_frames)
+    }
+
+    /// Multiply a duration by a number, returning `None` on overflow.
+    pub fn checked_mul(self, other: i64) -> Option<Self> {
+        self.frames.checked_mul(other).map(Self::from_frames)
+    }
+
+    /// Negate a duration, returning `None` on overflow.
+    pub fn checked_neg(self) -> Option<Self> {
+        self.frames.checked_neg().map(Self::from_frames)
+    }
+
+    /// Add two durations, stopping at the longest possible duration on
+    /// overflow.
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self::from_frames(self.frames.saturating_add(other.frames))
+    }
+
+    /// Subtract a duration, stopping at the longest possible duration on
+    /// overflow.
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self::from_frames(self.frames.saturating_sub(other.frames))
+    }
+
+    /// Multiply a duration by a number, stopping at the longest possible
+    /// duration on overflow.
+    pub fn saturating_mul(self, other: i64) -> Self {
+        Self::from_frames(self.frames.saturating_mul(other))
+    }
+
+    /// Add two durations, wrapping around on overflow.
+    pub fn wrapping_add(self, other: Self) -> Self {
+        Self::from_frames(self.frames.wrapping_add(other.frames))
+    }
+
+    /// Subtract a duration, wrapping around on overflow.
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        Self::from_frames(self.frames.wrapping_sub(other.frames))
+    }
+
+    /// Multiply a duration by a number, wrapping around on overflow.
+    pub fn wrapping_mul(self, other: i64) -> Self {
+        Self::from_frames(self.frames.wrapping_mul(other))
+    }
+}
+
+impl<T> TimecodeDuration<T>
+where
+    T: FrameRate,
+{
+    /// Split the absolute value of the duration into hours, minutes, seconds
+    /// and frames, counting frames the same way as a timecode does.
+    pub(crate) fn fields(&self) -> (u64, u8, u8, u8) {
+        let frames = self.frames.unsigned_abs();
+        let days = frames / T::MAX_FRAMES as u64;
+        let (hour, minute, second, frame) =
+            T::calculate_time_code((frames % T::MAX_FRAMES as u64) as u32);
+
+        (days * 24 + hour as u64, minute, second, frame)
+    }
+}
+
+impl<T> Clone for TimecodeDuration<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TimecodeDuration<T> {}
+
+impl<T> fmt::Debug for TimecodeDuration<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TimecodeDuration")
+            .field("frames", &self.frames)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for TimecodeDuration<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.frames == other.frames
+    }
+}
+
+impl<T> Eq for TimecodeDuration<T> {}
+
+impl<T> PartialOrd for TimecodeDuration<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for TimecodeDuration<T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.frames.cmp(&other.frames)
+    }
+}
+
+impl<T> hash::Hash for TimecodeDuration<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.frames.hash(state);
+    }
+}
+
+/// Format the duration as `HH:MM:SS:FF`, with a semicolon before the frame
+/// field for drop frame frame rates, and a leading `-` if the duration is
+/// negative. The hour field is not limited to 24 hours.
+///
+/// With the `+` flag, `{:+}`, positive durations and zero get a leading `+`.
+///
+/// ```
+/// use video_timecode::*;
+///
+/// let duration = TimecodeDuration::<FrameRate2997>::from_frames(-1800);
+/// assert_eq!(duration.to_string(), "-00:01:00;02");
+///
+/// let frames = 25 * 3600 * 30;
+/// let duration = TimecodeDuration::<FrameRate25>::from_frames(frames);
+/// assert_eq!(duration.to_string(), "30:00:00:00");
+/// assert_eq!(format!("{:+}", duration), "+30:00:00:00");
+/// ```
+impl<T> fmt::Display for TimecodeDuration<T>
+where
+    T: FrameRate,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (hour, minute, second, frame) = self.fields();
+
+        let separator = match T::DROP_FRAME {
+            true => ';',
+            false => ':',
+        };
+
+        let sign = if self.frames < 0 {
+            "-"
+        } else if f.sign_plus() {
+            "+"
+        } else {
+            ""
+        };
+
+        write!(
+            f,
+            "{}{:02}:{:02}:{:02}{}{:0width$}",
+            sign,
+            hour,
+            minute,
+            second,
+            separator,
+            frame,
+            width = frame_digits(T::FPS)
+        )
+    }
+}
+
+/// Parse a string into a duration.
+///
+/// This takes the same formats as an
+/// [ExtendedTimecode](struct.ExtendedTimecode.html), optionally starting with
+/// a `+` or `-` sign, so that negative offsets such as sync offsets and
+/// pre-roll can be read.
+///
+/// ```
+/// use video_timecode::*;
+/// use std::str::FromStr;
+///
+/// let offset = TimecodeDuration::<FrameRate25>::from_str("-00:00:01:12")
+///     .unwrap();
+/// assert_eq!(offset.frames(), -37);
+///
+/// let tc = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+/// assert_eq!((tc + offset).to_string(), "09:59:58:13");
+/// ```
+impl<T> str::FromStr for TimecodeDuration<T>
+where
+    T: FrameRate,
+{
+    type Err = TimecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, rest) = if let Some(rest) = s.strip_prefix('-') {
+            (true, rest)
+        } else if let Some(rest) = s.strip_prefix('+') {
+            (false, rest)
+        } else {
+            (false, s)
+        };
+
+        let offset = s.len() - rest.len();
+        let timecode: ExtendedTimecode<T> = rest
+            .parse()
+            .map_err(|error: TimecodeError| error.offset(offset))?;
+
+        match i64::try_from(timecode.frame_number) {
+            Ok(frames) if negative => Ok(Self::from_frames(-frames)),
+            Ok(frames) => Ok(Self::from_frames(frames)),
+            Err(_) => Err(TimecodeError::from(
+                TimecodeErrorKind::InvalidTimecode,
+            )),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty)*) => ($(
+        /// Create a duration of the given number of frames, which may be
+        /// negative.
+        impl<T> From<$t> for TimecodeDuration<T> {
+            fn from(frames: $t) -> Self {
+                TimecodeDuration::from_frames(frames as i64)
+            }
+        }
+    )*)
+}
+impl_from_int! { u8 u16 u32 i8 i16 i32 i64 }
+
+/// The duration between two timecodes. This is negative if the first
+/// timecode is earlier than the second.
+impl<T> ops::Sub for Timecode<T>
+where
+    T: FrameRate,
+{
+    type Output = TimecodeDuration<T>;
+
+    fn sub(self, other: Self) -> TimecodeDuration<T> {
+        TimecodeDuration::from_frames(
+            self.frame_number as i64 - other.frame_number as i64,
+        )
+    }
+}
+
+/// Move a timecode forward by a duration, or backward if the duration is
+/// negative. Like when adding frames, the timecode rolls over at 24 hours.
+impl<T> ops::Add<TimecodeDuration<T>> for Timecode<T>
+where
+    T: FrameRate,
+{
+    type Output = Timecode<T>;
+
+    fn add(self, other: TimecodeDuration<T>) -> Timecode<T> {
+        self + other.frames
+    }
+}
+
+impl<T> ops::AddAssign<TimecodeDuration<T>> for Timecode<T>
+where
+    T: FrameRate,
+{
+    fn add_assign(&mut self, other: TimecodeDuration<T>) {
+        *self += other.frames;
+    }
+}
+
+/// Move a timecode backward by a duration, or forward if the duration is
+/// negative. Like when subtracting frames, the timecode rolls over at 24
+/// hours.
+impl<T> ops::Sub<TimecodeDuration<T>> for Timecode<T>
+where
+    T: FrameRate,
+{
+    type Output = Timecode<T>;
+
+    fn sub(self, other: TimecodeDuration<T>) -> Timecode<T> {
+        self - other.frames
+    }
+}
+
+impl<T> ops::SubAssign<TimecodeDuration<T>> for Timecode<T>
+where
+    T: FrameRate,
+{
+    fn sub_assign(&mut self, other: TimecodeDuration<T>) {
+        *self -= other.frames;
+    }
+}
+
+impl<T> ops::Add for TimecodeDuration<T> {
+    type Output = TimecodeDuration<T>;
+
+    fn add(self, other: Self) -> Self {
+        TimecodeDuration::from_frames(self.frames + other.frames)
+    }
+}
+
+impl<T> ops::AddAssign for TimecodeDuration<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.frames += other.frames;
+    }
+}
+
+impl<T> ops::Sub for TimecodeDuration<T> {
+    type Output = TimecodeDuration<T>;
+
+    fn sub(self, other: Self) -> Self {
+        TimecodeDuration::from_frames(self.frames - other.frames)
+    }
+}
+
+impl<T> ops::SubAssign for TimecodeDuration<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.frames -= other.frames;
+    }
+}
+
+impl<T> ops::Neg for TimecodeDuration<T> {
+    type Output = TimecodeDuration<T>;
+
+    fn neg(self) -> Self {
+        TimecodeDuration::from_frames(-self.frames)
+    }
+}
+
+/// Multiply a duration by a number.
+impl<T> ops::Mul<i64> for TimecodeDuration<T> {
+    type Output = TimecodeDuration<T>;
+
+    fn mul(self, other: i64) -> Self {
+        TimecodeDuration::from_frames(self.frames * other)
+    }
+}
+
+impl<T> ops::MulAssign<i64> for TimecodeDuration<T> {
+    fn mul_assign(&mut self, other: i64) {
+        self.frames *= other;
+    }
+}
@@ -0,0 +1,436 @@
+//! Vertical interval timecode (VITC), as defined in SMPTE ST 12-1.
+//!
+//! VITC is recorded in lines of the vertical blanking interval of each
+//! field of standard definition video. Each line has a 90-bit word, made of
+//! nine groups of a sync pair, which is a one and a zero, followed by eight
+//! bits. The first eight groups carry the same 64 bits of data as an
+//! [LTC](../ltc/index.html) word, with the field mark flag in place of the
+//! polarity correction bit, and the last group carries a CRC of the word.
+//!
+//! A [VitcFrame](struct.VitcFrame.html) is encoded into twelve bytes, with
+//! the bits in the order they are sent: bit 0 of the word is the least
+//! significant bit of the first byte. It can be rendered into, and read
+//! from, a line of 8-bit luma samples with a [VitcLine](struct.VitcLine.html).
+//!
+//! ```
+//! use video_timecode::*;
+//! use video_timecode::vitc::*;
+//!
+//! let mut frame = VitcFrame::new(
+//!     Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap(),
+//! );
+//! frame.user_bits = 0x2026_1016;
+//! frame.field_mark = true;
+//!
+//! let bytes = frame.encode();
+//! assert_eq!(VitcFrame::<FrameRate25>::decode(&bytes).unwrap(), frame);
+//!
+//! let line_format = VitcLine::new(13_500_000);
+//! let mut line = [0; 864];
+//! line_format.render(&frame, &mut line);
+//! assert_eq!(line_format.read::<FrameRate25>(&line).unwrap(), frame);
+//! ```
+
+use core::time::Duration;
+
+use ltc::{flag_positions, pack_data, unpack_data};
+use {abs, FrameRate, FrameRate25, FrameRate2997, FrameRate2997Ndf,
+     FrameRate30, Timecode, TimecodeError, TimecodeErrorKind};
+
+/// Frame rates of the standard definition video that VITC is used in:
+/// 625 line video at 25 fps, and 525 line video at 29.97 or 30 fps.
+pub trait VitcFrameRate: FrameRate {}
+
+impl VitcFrameRate for FrameRate25 {}
+impl VitcFrameRate for FrameRate2997 {}
+impl VitcFrameRate for FrameRate2997Ndf {}
+impl VitcFrameRate for FrameRate30 {}
+
+/// The contents of a VITC word.
+///
+/// The drop frame flag is set from the frame rate, and the sync pairs and
+/// CRC are filled in when encoding.
+#[derive(Debug, PartialEq)]
+pub struct VitcFrame<FrameRate> {
+    pub timecode: Timecode<FrameRate>,
+
+    /// The eight groups of user bits, with the first group in the four
+    /// least significant bits.
+    pub user_bits: u32,
+
+    /// The color frame flag, set when the timecode is locked to the color
+    /// framing of the video.
+    pub color_frame: bool,
+
+    /// The binary group flags, which tell what the user bits hold. The
+    /// least significant bit is BGF0, and only the three lowest bits are
+    /// used.
+    pub binary_group_flags: u8,
+
+    /// The field mark flag, set in the word of the second field of each
+    /// frame.
+    pub field_mark: bool,
+}
+
+impl<T> Clone for VitcFrame<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for VitcFrame<T> {}
+
+impl<T> From<Timecode<T>> for VitcFrame<T>
+where
+    T: VitcFrameRate,
+{
+    fn from(timecode: Timecode<T>) -> Self {
+        VitcFrame::new(timecode)
+    }
+}
+
+const BITS: u32 = 90;
+
+/// The CRC in the last group of the word.
+const CRC: u32 = 82;
+
+impl<T> VitcFrame<T>
+where
+    T: VitcFrameRate,
+{
+    /// Returns the word for the first field of a frame with the given
+    /// timecode, and no user bits or flags.
+    pub fn new(timecode: Timecode<T>) -> VitcFrame<T> {
+        VitcFrame {
+            timecode,
+            user_bits: 0,
+            color_frame: false,
+            binary_group_flags: 0,
+            field_mark: false,
+        }
+    }
+
+    /// Encode the frame as a 90-bit VITC word, with bit 0 of the word as
+    /// the least significant bit of the first byte. The six highest bits of
+    /// the last byte are left unset.
+    pub fn encode(&self) -> [u8; 12] {
+        let mut data = pack_data(
+            &self.timecode,
+            self.user_bits,
+            self.color_frame,
+            self.binary_group_flags,
+        );
+        data |= (self.field_mark as u64) << flag_positions::<T>().polarity;
+
+        let mut word = 0;
+
+        for group in 0..8 {
+            let bits = (data >> (8 * group)) as u8;
+
+            word |= (0b01 | (bits as u128) << 2) << (10 * group);
+        }
+
+        word |= 0b01 << 80;
+        word |= (crc(word) as u128) << CRC;
+
+        let mut bytes = [0; 12];
+        bytes.copy_from_slice(&word.to_le_bytes()[..12]);
+        bytes
+    }
+
+    /// Decode a 90-bit VITC word, with bit 0 of the word as the least
+    /// significant bit of the first byte. The six highest bits of the last
+    /// byte are ignored.
+    ///
+    /// If a sync pair is missing or the CRC does not match, or if a digit
+    /// is not valid BCD, an error with the `TimecodeErrorKind`
+    /// [InvalidFormat](../enum.TimecodeErrorKind.html#variant.InvalidFormat)
+    /// is returned. If the drop frame flag does not match the frame rate,
+    /// it is
+    /// [FrameRateMismatch](../enum.TimecodeErrorKind.html#variant.FrameRateMismatch),
+    /// and if the timecode is not valid for the frame rate, it is
+    /// [InvalidTimecode](../enum.TimecodeErrorKind.html#variant.InvalidTimecode).
+    pub fn decode(bytes: &[u8; 12]) -> Result<VitcFrame<T>, TimecodeError> {
+        let mut word = [0; 16];
+        word[..12].copy_from_slice(bytes);
+        let word = u128::from_le_bytes(word) & ((1 << BITS) - 1);
+
+        let sync_pairs =
+            (0..9).all(|group| (word >> (10 * group)) & 0b11 == 0b01);
+
+        if !sync_pairs || (word >> CRC) as u8 != crc(word) {
+            return Err(TimecodeError::from(TimecodeErrorKind::InvalidFormat));
+        }
+
+        let mut data = 0;
+
+        for group in 0..8 {
+            let bits = (word >> (10 * group + 2)) as u8;
+
+            data |= (bits as u64) << (8 * group);
+        }
+
+        let field_mark = (data >> flag_positions::<T>().polarity) & 1 == 1;
+        let data = unpack_data(data)?;
+
+        Ok(VitcFrame {
+            timecode: data.timecode,
+            user_bits: data.user_bits,
+            color_frame: data.color_frame,
+            binary_group_flags: data.binary_group_flags,
+            field_mark,
+        })
+    }
+}
+
+/// The CRC of bits 0 to 81 of a word, with the generator polynomial
+/// `x^8 + 1`.
+///
+/// Dividing by `x^8 + 1` folds the bits onto each other eight at a time, so
+/// each bit of the CRC is the exclusive or of every eighth bit of the word,
+/// lined up with the positions of the CRC bits.
+fn crc(word: u128) -> u8 {
+    let mut folded = 0;
+
+    for bit in 0..CRC {
+        folded ^= ((word >> bit) as u8 & 1) << (bit % 8);
+    }
+
+    // The CRC starts at bit 82, which lines up with bit 2 of the fold.
+    folded.rotate_right(CRC % 8)
+}
+
+/// The format of a video line that VITC is rendered into or read from, as
+/// 8-bit luma samples.
+///
+/// The bit rate is set by the line rate: 116 bits for each line of 625 line
+/// video, and 113.75 bits for each line of 525 line video, which is half of
+/// the color subcarrier.
+///
+/// ```
+/// use video_timecode::*;
+/// use video_timecode::vitc::*;
+///
+/// let tc = Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap();
+/// let line_format = VitcLine::new(27_000_000).levels(0, 200);
+///
+/// let mut line = [0; 1716];
+/// line_format.render(&VitcFrame::new(tc), &mut line);
+///
+/// let frame = line_format.read::<FrameRate2997>(&line).unwrap();
+/// assert_eq!(frame.timecode.to_string(), "01:00:00;00");
+/// ```
+#[derive(Debug, Clone)]
+pub struct VitcLine {
+    sample_rate: u32,
+    start: Duration,
+    rise_time: Duration,
+    black: u8,
+    white: u8,
+}
+
+impl VitcLine {
+    /// Returns the format of a line sampled at the given rate in Hz, like
+    /// 13.5 MHz for Rec. ITU-R BT.601 video.
+    ///
+    /// By default, the first sample is at the start of the line, where the
+    /// horizontal sync starts. The word starts 11.2 µs later, has a rise
+    /// time of 200 ns, and goes from a black level of 16 to a level of 191
+    /// for ones, which is 80% of the way to white in BT.601 levels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` is zero.
+    pub fn new(sample_rate: u32) -> VitcLine {
+        assert!(sample_rate > 0, "Sample rate must be positive.");
+
+        VitcLine {
+            sample_rate,
+            start: Duration::from_nanos(11_200),
+            rise_time: Duration::from_nanos(200),
+            black: 16,
+            white: 191,
+        }
+    }
+
+    /// Set the time from the first sample to the start of the word.
+    pub fn start(self, start: Duration) -> VitcLine {
+        VitcLine { start, ..self }
+    }
+
+    /// Set the time the signal takes to go from 10% to 90% of a transition.
+    /// The transitions are straight ramps, and a rise time of zero gives
+    /// sharp edges.
+    pub fn rise_time(self, rise_time: Duration) -> VitcLine {
+        VitcLine { rise_time, ..self }
+    }
+
+    /// Set the levels of zeros, which is also the level of the rest of the
+    /// line, and of ones.
+    pub fn levels(self, black: u8, white: u8) -> VitcLine {
+        VitcLine {
+            black,
+            white,
+            ..self
+        }
+    }
+
+    /// The length of a bit, in samples.
+    fn bit_length<T>(&self) -> f64
+    where
+        T: VitcFrameRate,
+    {
+        let bits_per_frame = if T::FPS == 25 {
+            625.0 * 116.0
+        } else {
+            525.0 * 113.75
+        };
+
+        self.sample_rate as f64 * T::RATE_DENOMINATOR as f64
+            / (T::RATE_NUMERATOR as f64 * bits_per_frame)
+    }
+
+    /// Render the word for a frame into a line. The samples outside of the
+    /// word are set to the black level.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line ends before the word does.
+    pub fn render<T>(&self, frame: &VitcFrame<T>, line: &mut [u8])
+    where
+        T: VitcFrameRate,
+    {
+        let bit_length = self.bit_length::<T>();
+        let start = self.start.as_secs_f64() * self.sample_rate as f64;
+
+        assert!(
+            line.len() as f64 >= start + BITS as f64 * bit_length,
+            "Line is too short for the word."
+        );
+
+        let mut word = [0; 16];
+        word[..12].copy_from_slice(&frame.encode());
+        let word = u128::from_le_bytes(word);
+
+        let black = self.black as f64;
+        let white = self.white as f64;
+        let level = |bit: i64| {
+            if (0..BITS as i64).contains(&bit) && (word >> bit) & 1 == 1 {
+                white
+            } else {
+                black
+            }
+        };
+
+        // A straight ramp from 0% to 100% takes 1.25 times the rise time.
+        // Measured in bits, it can take at most a whole bit.
+        let rise_time = self.rise_time.as_secs_f64() * self.sample_rate as f64;
+        let ramp = (rise_time * 1.25 / bit_length).min(1.0);
+
+        // Positions before the word are negative, so they are shifted by a
+        // whole number of bits to round them down.
+        let shift = (start / bit_length) as i64 + 1;
+        let floor = |position: f64| (position + shift as f64) as i64 - shift;
+
+        for (i, sample) in line.iter_mut().enumerate() {
+            // Position of the sample within the word, in bits, and the
+            // start of a bit nearest to it.
+            let position = (i as f64 - start) / bit_length;
+            let nearest = floor(position + 0.5);
+            let from_nearest = position - nearest as f64;
+
+            let value = if abs(from_nearest) < ramp / 2.0 {
+                let before = level(nearest - 1);
+                let after = level(nearest);
+
+                before + (after - before) * (from_nearest / ramp + 0.5)
+            } else {
+                level(floor(position))
+            };
+
+            *sample = (value + 0.5) as u8;
+        }
+    }
+
+    /// Read the word from a line.
+    ///
+    /// The word is found by its first rising edge, wherever it is in the
+    /// line, and the falling edge in each sync pair is used to follow the
+    /// bits, so that timing errors in captures of tape are tolerated. The
+    /// level of the line is not used, so it does not need to be set.
+    ///
+    /// If no word is found in the line, an error with the
+    /// `TimecodeErrorKind`
+    /// [InvalidFormat](../enum.TimecodeErrorKind.html#variant.InvalidFormat)
+    /// is returned. Otherwise the word is decoded like with
+    /// [VitcFrame::decode](struct.VitcFrame.html#method.decode).
+    pub fn read<T>(&self, line: &[u8]) -> Result<VitcFrame<T>, TimecodeError>
+    where
+        T: VitcFrameRate,
+    {
+        let invalid_format =
+            || TimecodeError::from(TimecodeErrorKind::InvalidFormat);
+
+        let min = line.iter().cloned().min().unwrap_or(0);
+        let max = line.iter().cloned().max().unwrap_or(0);
+
+        // Too little difference for there to be a word, rather than noise
+        // or a black line.
+        if max - min < 32 {
+            return Err(invalid_format());
+        }
+
+        let threshold = (min as f64 + max as f64) / 2.0;
+        let bit_length = self.bit_length::<T>();
+
+        // Where the signal crosses the threshold between sample `i - 1` and
+        // sample `i`, going up or down.
+        let crossing = |i: usize, rising: bool| {
+            let before = line[i - 1] as f64;
+            let after = line[i] as f64;
+
+            if (before < threshold) == rising && (after < threshold) != rising
+            {
+                Some(i as f64 - 1.0 + (threshold - before) / (after - before))
+            } else {
+                None
+            }
+        };
+
+        let mut edge = (1..line.len())
+            .filter_map(|i| crossing(i, true))
+            .next()
+            .ok_or_else(invalid_format)?;
+
+        let mut word = 0u128;
+
+        for group in 0..9 {
+            // The falling edge in the middle of the sync pair, which should
+            // be a bit after the start of the group.
+            let expected = edge + bit_length;
+            let from = (expected - bit_length / 2.0).max(1.0) as usize;
+            let to = ((expected + bit_length / 2.0) as usize).min(line.len());
+
+            let falling = (from..to)
+                .filter_map(|i| crossing(i, false))
+                .next()
+                .ok_or_else(invalid_format)?;
+
+            for bit in 0..10 {
+                let center = falling + (bit as f64 - 0.5) * bit_length;
+                let sample = line.get((center + 0.5) as usize);
+
+                if *sample.ok_or_else(invalid_format)? as f64 >= threshold {
+                    word |= 1 << (10 * group + bit);
+                }
+            }
+
+            edge = falling + 9.0 * bit_length;
+        }
+
+        let mut bytes = [0; 12];
+        bytes.copy_from_slice(&word.to_le_bytes()[..12]);
+
+        VitcFrame::decode(&bytes)
+    }
+}
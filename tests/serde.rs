@@ -0,0 +1,93 @@
+#![cfg(feature = "serde")]
+
+#[macro_use]
+extern crate serde;
+extern crate serde_json;
+extern crate video_timecode;
+
+use video_timecode::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Clip {
+    start: Timecode<FrameRate2997>,
+
+    #[serde(with = "video_timecode::serde::frame_number")]
+    end: Timecode<FrameRate2997>,
+
+    #[serde(with = "video_timecode::serde::fields")]
+    poster: Timecode<FrameRate2997>,
+}
+
+#[test]
+fn serialize_representations() {
+    let clip = Clip {
+        start: Timecode::new(1, 0, 0, 0).unwrap(),
+        end: Timecode::new(1, 0, 1, 0).unwrap(),
+        poster: Timecode::new(1, 0, 0, 12).unwrap(),
+    };
+
+    let json = serde_json::to_string(&clip).unwrap();
+    assert_eq!(
+        json,
+        r#"{"start":"01:00:00;00","end":107922,"poster":{"hour":1,"minute":0,"second":0,"frame":12}}"#
+    );
+
+    assert_eq!(serde_json::from_str::<Clip>(&json).unwrap(), clip);
+}
+
+#[test]
+fn deserialize_string() {
+    let tc: Timecode<FrameRate25> =
+        serde_json::from_str(r#""10:00:00:00""#).unwrap();
+    assert_eq!(tc.frame_number, 900000);
+
+    let error = serde_json::from_str::<Timecode<FrameRate25>>(
+        r#""10:00:00:25""#,
+    )
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("invalid timecode: frame 25 is not between 0 and 24"));
+
+    assert!(serde_json::from_str::<Timecode<FrameRate25>>("900000").is_err());
+}
+
+#[test]
+fn deserialize_frame_number() {
+    #[derive(Debug, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "video_timecode::serde::frame_number")]
+        tc: Timecode<FrameRate24>,
+    }
+
+    let wrapper: Wrapper = serde_json::from_str(r#"{"tc":2073599}"#).unwrap();
+    assert_eq!(wrapper.tc.to_string(), "23:59:59:23");
+
+    assert!(serde_json::from_str::<Wrapper>(r#"{"tc":2073600}"#).is_err());
+    assert!(serde_json::from_str::<Wrapper>(r#"{"tc":-1}"#).is_err());
+}
+
+#[test]
+fn deserialize_fields() {
+    #[derive(Debug, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "video_timecode::serde::fields")]
+        tc: Timecode<FrameRate2997>,
+    }
+
+    let json = r#"{"tc":{"hour":0,"minute":1,"second":0,"frame":2}}"#;
+    let wrapper: Wrapper = serde_json::from_str(json).unwrap();
+    assert_eq!(wrapper.tc.frame_number, 1800);
+
+    // A dropped frame.
+    let json = r#"{"tc":{"hour":0,"minute":1,"second":0,"frame":0}}"#;
+    let error = serde_json::from_str::<Wrapper>(json).unwrap_err();
+    assert!(error.to_string().contains("dropped in drop frame timecode"));
+
+    let json = r#"{"tc":{"hour":0,"minute":1,"second":0}}"#;
+    assert!(serde_json::from_str::<Wrapper>(json).is_err());
+
+    let json =
+        r#"{"tc":{"hour":0,"minute":1,"second":0,"frame":2,"field":1}}"#;
+    assert!(serde_json::from_str::<Wrapper>(json).is_err());
+}
@@ -1,5 +1,9 @@
+// Some fields are written with leading zeros, like in a timecode.
+#![allow(clippy::zero_prefixed_literal)]
+
 extern crate video_timecode;
 
+use std::convert::TryFrom;
 use std::str::FromStr;
 
 use video_timecode::*;
@@ -309,11 +313,42 @@ test_factories!(
     1134376
 );
 
+// Minutes ending in 1 drop frames too, like the other minutes not divisible
+// by ten.
+test_factories!(
+    frame_number_2997_first_minute,
+    0,
+    1,
+    0,
+    2,
+    FrameRate2997,
+    1800
+);
+test_factories!(
+    frame_number_2997_eleventh_minute,
+    0,
+    11,
+    1,
+    0,
+    FrameRate2997,
+    19810
+);
+test_factories!(
+    frame_number_5994_minute_ending_in_one,
+    1,
+    21,
+    0,
+    4,
+    FrameRate5994,
+    291312
+);
+
 #[test]
 fn dropped_timecode_2997() {
     match Timecode::<FrameRate2997>::new(23, 23, 00, 1) {
         Err(TimecodeError {
             kind: video_timecode::TimecodeErrorKind::InvalidTimecode,
+            ..
         }) => {}
         _ => panic!(),
     }
@@ -324,6 +359,7 @@ fn dropped_timecode_5994() {
     match Timecode::<FrameRate5994>::new(10, 01, 00, 3) {
         Err(TimecodeError {
             kind: video_timecode::TimecodeErrorKind::InvalidTimecode,
+            ..
         }) => {}
         _ => panic!(),
     }
@@ -489,6 +525,7 @@ fn parse_0_df_mixed() {
     match Timecode::<FrameRate2997>::from_str("00.00:00.00") {
         Err(TimecodeError {
             kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+            ..
         }) => {}
         _ => panic!(),
     }
@@ -499,6 +536,7 @@ fn parse_0_df_for_ndf_frame_rate() {
     match Timecode::<FrameRate24>::from_str("00:00:00;00") {
         Err(TimecodeError {
             kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+            ..
         }) => {}
         _ => panic!(),
     }
@@ -537,3 +575,2466 @@ fn format_df() {
         "10:00:20;10"
     );
 }
+
+// Test timecodes with frame rates selected at runtime
+
+#[test]
+fn any_frame_rate_matches_types() {
+    assert_eq!(
+        AnyFrameRate::of::<FrameRate2398>(),
+        Some(AnyFrameRate::Fps2398)
+    );
+    assert_eq!(AnyFrameRate::Fps2398.fps(), FrameRate2398::FPS);
+    assert_eq!(AnyFrameRate::Fps5994.drop_frame(), FrameRate5994::DROP_FRAME);
+    assert_eq!(AnyFrameRate::new(24, 1, false), Some(AnyFrameRate::Fps24));
+    assert_eq!(AnyFrameRate::new(24000, 1001, true), None);
+}
+
+#[test]
+fn any_frame_rate_round_trip_string() {
+    for frame_rate in AnyFrameRate::ALL {
+        let s = frame_rate.to_string();
+        assert_eq!(AnyFrameRate::from_str(&s).unwrap(), *frame_rate);
+    }
+}
+
+#[test]
+fn any_frame_rate_df_suffix_for_ndf_rate() {
+    assert!(AnyFrameRate::from_str("25DF").is_err());
+}
+
+#[test]
+fn any_timecode_new_2997() {
+    let tc = AnyTimecode::new(AnyFrameRate::Fps2997, 23, 23, 23, 1).unwrap();
+    assert_tc!(tc, 23, 23, 23, 1, 2523565);
+}
+
+#[test]
+fn any_timecode_dropped_frame() {
+    match AnyTimecode::new(AnyFrameRate::Fps2997, 23, 23, 0, 1) {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidTimecode,
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn any_timecode_add_and_subtract() {
+    let tc = AnyTimecode::new(AnyFrameRate::Fps2997, 0, 0, 10, 0).unwrap();
+    assert_tc!(tc + (-1000i32), 23, 59, 36, 20, 2588708);
+    assert_tc!(tc - 1000u32, 23, 59, 36, 20, 2588708);
+    assert_tc!(tc + 1000000u64, 9, 16, 16, 22, 1000300);
+}
+
+#[test]
+fn any_timecode_parse_and_format() {
+    let tc = AnyTimecode::from_str("10:00:20;10@59.94DF").unwrap();
+    assert_eq!(tc.frame_rate(), AnyFrameRate::Fps5994);
+    assert_eq!(tc.to_string(), "10:00:20;10");
+    assert_eq!(format!("{:#}", tc), "10:00:20;10@59.94DF");
+    assert_eq!(format!("{:>14}", tc), "   10:00:20;10");
+    assert_eq!(format!("{:*<14}", tc), "10:00:20;10***");
+    assert_eq!(format!("{:^#21}", tc), " 10:00:20;10@59.94DF ");
+}
+
+#[test]
+fn any_timecode_parse_without_frame_rate() {
+    match AnyTimecode::from_str("10:00:20;10") {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+            reason:
+                Some(TimecodeErrorReason::UnexpectedEnd { expected: "'@'" }),
+            position: Some(11),
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn any_timecode_parse_df_for_ndf_frame_rate() {
+    match AnyTimecode::parse(AnyFrameRate::Fps25, "00:00:00;00") {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn any_timecode_round_trip_typed() {
+    use std::convert::TryFrom;
+
+    let tc = Timecode::<FrameRate2997>::new(19, 16, 6, 22).unwrap();
+    let any = AnyTimecode::try_from(tc).unwrap();
+    assert_tc!(any, 19, 16, 6, 22, 2078920);
+    assert_eq!(
+        Timecode::<FrameRate2997>::try_from(any).unwrap(),
+        Timecode::<FrameRate2997>::new(19, 16, 6, 22).unwrap()
+    );
+    assert!(Timecode::<FrameRate30>::try_from(any).is_err());
+}
+
+// Test exact frame rates
+
+#[test]
+fn rate_2398_differs_from_24() {
+    assert_eq!(
+        (FrameRate24::RATE_NUMERATOR, FrameRate24::RATE_DENOMINATOR),
+        (24, 1)
+    );
+    assert_eq!(
+        (FrameRate2398::RATE_NUMERATOR, FrameRate2398::RATE_DENOMINATOR),
+        (24000, 1001)
+    );
+    assert_ne!(
+        FrameRate24::frame_duration(),
+        FrameRate2398::frame_duration()
+    );
+}
+
+#[test]
+fn frames_to_duration_2997_hour() {
+    // 01:00:00;00 in 29.97 drop frame.
+    assert_eq!(
+        FrameRate2997::frames_to_duration(107892),
+        std::time::Duration::from_micros(3_599_996_400)
+    );
+    assert_eq!(
+        AnyFrameRate::Fps2997.frames_to_duration(107892),
+        FrameRate2997::frames_to_duration(107892)
+    );
+}
+
+#[test]
+fn frames_to_seconds_ndf() {
+    assert_eq!(FrameRate50::frames_to_seconds(125), 2.5);
+    assert_eq!(AnyFrameRate::Fps2398.rate(), (24000, 1001));
+}
+
+#[derive(Debug, PartialEq)]
+struct OldFrameRate2997;
+
+impl FrameRate for OldFrameRate2997 {
+    const FPS: u32 = 30;
+    const DROP_FRAME: bool = true;
+    const MAX_FRAMES: u32 = 2589408;
+}
+
+#[test]
+fn frame_rate_defaults() {
+    assert_eq!(
+        (
+            OldFrameRate2997::RATE_NUMERATOR,
+            OldFrameRate2997::RATE_DENOMINATOR,
+        ),
+        (30000, 1001)
+    );
+    assert_eq!(OldFrameRate2997::ANY_FRAME_RATE, None);
+
+    let tc = Timecode::<OldFrameRate2997>::new(1, 0, 0, 0).unwrap();
+    assert_eq!(tc.to_string(), "01:00:00;00");
+    match AnyTimecode::try_from(tc) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::FrameRateMismatch,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+}
+
+// Test conversion to and from real time
+
+#[test]
+fn to_duration_2398() {
+    let tc = Timecode::<FrameRate2398>::new(1, 0, 0, 0).unwrap();
+    assert_eq!(tc.to_duration(), std::time::Duration::from_millis(3_603_600));
+}
+
+#[test]
+fn from_duration_round_trip_5994() {
+    for &frame_number in &[0u32, 1, 2, 59, 1000, 107892, 5178815] {
+        let tc = Timecode::<FrameRate5994>::from(frame_number);
+        assert_eq!(
+            Timecode::<FrameRate5994>::from_duration(
+                tc.to_duration(),
+                Rounding::Nearest
+            ),
+            tc
+        );
+        assert_eq!(
+            Timecode::<FrameRate5994>::from_seconds(
+                tc.to_seconds(),
+                Rounding::Nearest
+            ),
+            tc
+        );
+    }
+}
+
+#[test]
+fn from_millis_rounding() {
+    // 30 ms is three quarters of a frame at 25 fps.
+    let floor = Timecode::<FrameRate25>::from_millis(30, Rounding::Floor);
+    let nearest = Timecode::<FrameRate25>::from_millis(30, Rounding::Nearest);
+    let ceil = Timecode::<FrameRate25>::from_millis(40, Rounding::Ceil);
+    assert_tc!(floor, 0, 0, 0, 0, 0);
+    assert_tc!(nearest, 0, 0, 0, 1, 1);
+    assert_tc!(ceil, 0, 0, 0, 1, 1);
+}
+
+#[test]
+fn from_millis_halfway() {
+    let tc = Timecode::<FrameRate25>::from_millis(20, Rounding::Nearest);
+    assert_tc!(tc, 0, 0, 0, 1, 1);
+}
+
+#[test]
+#[should_panic(expected = "Time can not be negative.")]
+fn from_seconds_negative() {
+    Timecode::<FrameRate25>::from_seconds(-0.04, Rounding::Floor);
+}
+
+#[test]
+fn from_real_time_large() {
+    let tc = Timecode::<FrameRate11988>::from_duration(
+        std::time::Duration::MAX,
+        Rounding::Ceil,
+    );
+    assert!(tc.frame_number < FrameRate11988::MAX_FRAMES);
+
+    let tc = Timecode::<FrameRate25>::from_seconds(1e19, Rounding::Floor);
+    assert!(tc.frame_number < FrameRate25::MAX_FRAMES);
+}
+
+#[test]
+#[should_panic(expected = "Time is out of range.")]
+fn from_seconds_out_of_range() {
+    Timecode::<FrameRate25>::from_seconds(1e300, Rounding::Nearest);
+}
+
+// Test durations between timecodes
+
+#[test]
+fn subtract_timecodes() {
+    let tc1 = Timecode::<FrameRate2997>::new(10, 0, 0, 0).unwrap();
+    let tc2 = Timecode::<FrameRate2997>::new(19, 16, 6, 22).unwrap();
+    assert_eq!((tc2 - tc1).frames(), 1000000);
+    assert_eq!((tc1 - tc2).frames(), -1000000);
+    assert_eq!((tc2 - tc1).to_string(), "09:16:06;22");
+}
+
+#[test]
+fn add_duration_rolls_over() {
+    let tc = Timecode::<FrameRate24>::new(23, 59, 30, 0).unwrap();
+    let duration = TimecodeDuration::<FrameRate24>::from_frames(24 * 60);
+    assert_eq!(
+        tc + duration,
+        Timecode::<FrameRate24>::new(0, 0, 30, 0).unwrap()
+    );
+    assert_eq!(tc - -duration, tc + duration);
+}
+
+#[test]
+fn add_assign_and_sub_assign_duration() {
+    let mut tc = Timecode::<FrameRate5994>::new(0, 0, 10, 0).unwrap();
+    tc -= TimecodeDuration::from_frames(1000);
+    assert_tc!(tc, 23, 59, 53, 20, 5178416);
+    tc += TimecodeDuration::from_frames(1000);
+    assert_tc!(tc, 0, 0, 10, 0, 600);
+}
+
+#[test]
+fn duration_arithmetic() {
+    let one = TimecodeDuration::<FrameRate25>::from_frames(25);
+    let mut total = one * 3 - one + one;
+    assert_eq!(total.frames(), 75);
+    total -= one * 4;
+    assert_eq!(total, -one);
+    total *= -2;
+    assert_eq!(total.to_string(), "00:00:02:00");
+    assert!(-one < one);
+}
+
+#[test]
+fn duration_format_beyond_24_hours_df() {
+    // 24 hours of 29.97 drop frame is 2589408 frames.
+    let duration = TimecodeDuration::<FrameRate2997>::from_frames(2591208);
+    assert_eq!(duration.to_string(), "24:01:00;02");
+}
+
+// Test checked, saturating and wrapping arithmetic
+
+#[test]
+fn subtract_unsigned_past_midnight() {
+    let tc = Timecode::<FrameRate24>::new(0, 0, 0, 0).unwrap() - 1u32;
+    assert_tc!(tc, 23, 59, 59, 23, 2073599);
+
+    let mut tc = Timecode::<FrameRate24>::new(0, 0, 0, 0).unwrap();
+    tc -= 1usize;
+    assert_tc!(tc, 23, 59, 59, 23, 2073599);
+}
+
+#[test]
+fn from_small_integer_types() {
+    let tc = Timecode::<FrameRate24>::from(200u8);
+    assert_tc!(tc, 0, 0, 8, 8, 200);
+
+    let tc = Timecode::<FrameRate24>::new(0, 0, 20, 0).unwrap() + 1u8;
+    assert_tc!(tc, 0, 0, 20, 1, 481);
+
+    let tc = Timecode::<FrameRate24>::from(100i8);
+    assert_tc!(tc, 0, 0, 4, 4, 100);
+
+    let tc = Timecode::<FrameRate24>::from(2073600i64);
+    assert_tc!(tc, 0, 0, 0, 0, 0);
+}
+
+#[test]
+#[should_panic(expected = "Frame number can not be negative.")]
+fn from_negative_frame_number() {
+    // This used to give 23:59:59:23.
+    let _ = Timecode::<FrameRate24>::from(-1);
+}
+
+#[test]
+#[should_panic(expected = "Frame number can not be negative.")]
+fn any_timecode_from_negative_frame_number() {
+    AnyTimecode::from_frame_number(AnyFrameRate::Fps25, -1);
+}
+
+#[test]
+fn negative_offsets_are_durations() {
+    let offset = TimecodeDuration::<FrameRate24>::from(-1i64);
+    assert_eq!(offset.frames(), -1);
+    assert_eq!(offset.to_string(), "-00:00:00:01");
+
+    let tc = Timecode::<FrameRate24>::new(1, 0, 0, 0).unwrap() + offset;
+    assert_tc!(tc, 0, 59, 59, 23, 86399);
+}
+
+#[test]
+fn checked_add_midnight_crossing() {
+    let tc = Timecode::<FrameRate2997>::new(23, 59, 59, 29).unwrap();
+    assert_eq!(tc.checked_add(1), None);
+    assert_eq!(tc.checked_sub(i64::MIN), None);
+    assert_tc!(tc.checked_sub(29).unwrap(), 23, 59, 59, 0, 2589378);
+
+    let tc = Timecode::<FrameRate2997>::new(0, 0, 0, 0).unwrap();
+    assert_eq!(tc.checked_sub(1), None);
+    assert_eq!(tc.checked_add(-1), None);
+}
+
+#[test]
+fn saturating_add_and_sub() {
+    let tc = Timecode::<FrameRate5994>::new(12, 0, 0, 0).unwrap();
+    assert_tc!(tc.saturating_add(i64::MAX), 23, 59, 59, 59, 5178815);
+    assert_tc!(tc.saturating_sub(i64::MAX), 0, 0, 0, 0, 0);
+    assert_tc!(tc.saturating_sub(i64::MIN), 23, 59, 59, 59, 5178815);
+}
+
+#[test]
+fn wrapping_add_and_sub() {
+    let tc = Timecode::<FrameRate2997>::new(0, 0, 10, 0).unwrap();
+    assert_eq!(tc.wrapping_add(-1000), tc + (-1000i64));
+    assert_eq!(tc.wrapping_sub(1000), tc - 1000u64);
+    assert_tc!(tc.wrapping_sub(1000), 23, 59, 36, 20, 2588708);
+}
+
+#[test]
+fn duration_checked_arithmetic() {
+    let min = TimecodeDuration::<FrameRate24>::from_frames(i64::MIN);
+    let one = TimecodeDuration::<FrameRate24>::from_frames(1);
+    assert_eq!(min.checked_neg(), None);
+    assert_eq!(min.checked_sub(one), None);
+    assert_eq!(min.checked_mul(2), None);
+    assert_eq!(one.checked_mul(2), Some(one + one));
+    assert_eq!(min.saturating_sub(one), min);
+    assert_eq!(min.saturating_mul(2), min);
+    assert_eq!(min.wrapping_sub(one).frames(), i64::MAX);
+    assert_eq!(min.wrapping_mul(2).frames(), 0);
+}
+
+// Test frame rate conversion
+
+#[test]
+fn convert_real_time_24_to_2398() {
+    let tc = Timecode::<FrameRate24>::new(1, 0, 0, 0).unwrap();
+    let converted = tc
+        .convert::<FrameRate2398>(Conversion::RealTime(Rounding::Nearest))
+        .unwrap();
+    // 3600 s at 23.976 fps is 86313.69 frames.
+    assert_tc!(converted, 0, 59, 56, 10, 86314);
+}
+
+#[test]
+fn convert_real_time_round_trip_2997_5994() {
+    let tc = Timecode::<FrameRate2997>::new(19, 16, 6, 22).unwrap();
+    let up = tc
+        .convert::<FrameRate5994>(Conversion::RealTime(Rounding::Floor))
+        .unwrap();
+    assert_tc!(up, 19, 16, 6, 44, 4157840);
+    let down = up
+        .convert::<FrameRate2997>(Conversion::RealTime(Rounding::Floor))
+        .unwrap();
+    assert_eq!(down, tc);
+}
+
+#[test]
+fn convert_real_time_rounding() {
+    let tc = Timecode::<FrameRate50>::new(0, 0, 0, 1).unwrap();
+    let floor = tc
+        .convert::<FrameRate25>(Conversion::RealTime(Rounding::Floor))
+        .unwrap();
+    let ceil = tc
+        .convert::<FrameRate25>(Conversion::RealTime(Rounding::Ceil))
+        .unwrap();
+    assert_eq!(floor.frame_number, 0);
+    assert_eq!(ceil.frame_number, 1);
+}
+
+#[test]
+fn convert_label_25_to_50_and_back() {
+    let policy = Conversion::Label(DroppedFramePolicy::Reject);
+    let tc = Timecode::<FrameRate25>::new(10, 0, 0, 12).unwrap();
+    let up = tc.convert::<FrameRate50>(policy).unwrap();
+    assert_tc!(up, 10, 0, 0, 24, 1800024);
+    assert_eq!(up.convert::<FrameRate25>(policy).unwrap(), tc);
+    let odd = (up + 1u32).convert::<FrameRate25>(policy).unwrap();
+    assert_eq!(odd, tc);
+}
+
+#[test]
+fn convert_label_2997_to_5994() {
+    let policy = Conversion::Label(DroppedFramePolicy::Reject);
+    let tc = Timecode::<FrameRate2997>::new(0, 1, 0, 2).unwrap();
+    let up = tc.convert::<FrameRate5994>(policy).unwrap();
+    assert_eq!(up.to_string(), "00:01:00;04");
+}
+
+#[test]
+fn convert_label_dropped_frame_policies() {
+    let tc = Timecode::<FrameRate60>::new(0, 1, 0, 2).unwrap();
+
+    match tc.convert::<FrameRate2997>(Conversion::Label(
+        DroppedFramePolicy::Reject,
+    )) {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidTimecode,
+            reason:
+                Some(TimecodeErrorReason::DroppedFrame {
+                    minute: 1,
+                    frame: 1,
+                }),
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+
+    let next = tc
+        .convert::<FrameRate2997>(Conversion::Label(DroppedFramePolicy::Next))
+        .unwrap();
+    assert_eq!(next.to_string(), "00:01:00;02");
+
+    let previous = tc
+        .convert::<FrameRate2997>(Conversion::Label(
+            DroppedFramePolicy::Previous,
+        ))
+        .unwrap();
+    assert_eq!(previous.to_string(), "00:00:59;29");
+}
+
+#[test]
+fn frame_number_first_minute_after_ten_df() {
+    let tc = Timecode::<FrameRate2997>::new(0, 1, 0, 2).unwrap();
+    assert_tc!(tc, 0, 1, 0, 2, 1800);
+    assert_eq!(Timecode::<FrameRate2997>::from(1800u32), tc);
+
+    let tc = Timecode::<FrameRate5994>::new(0, 11, 0, 4).unwrap();
+    assert_tc!(tc, 0, 11, 0, 4, 39564);
+    assert_eq!(Timecode::<FrameRate5994>::from(39564u32), tc);
+}
+
+// Test pulldown
+
+fn pulldown_at_zero(pattern: pulldown::PulldownPattern) -> pulldown::Pulldown {
+    pulldown::Pulldown::new(
+        pattern,
+        Timecode::<FrameRate2398>::new(0, 0, 0, 0).unwrap(),
+        Timecode::<FrameRate2997>::new(0, 0, 0, 0).unwrap(),
+    )
+}
+
+#[test]
+fn pulldown_standard_video_cadence() {
+    use video_timecode::pulldown::CadenceFrame::*;
+    use video_timecode::pulldown::*;
+
+    let pulldown = pulldown_at_zero(PulldownPattern::Standard);
+    let cadence: Vec<_> = (0u32..5)
+        .map(|n| pulldown.video_cadence(Timecode::<FrameRate2997>::from(n)))
+        .collect();
+    assert_eq!(cadence, vec![(A, A), (B, B), (B, C), (C, D), (D, D)]);
+}
+
+#[test]
+fn pulldown_advanced_video_cadence() {
+    use video_timecode::pulldown::CadenceFrame::*;
+    use video_timecode::pulldown::*;
+
+    let pulldown = pulldown_at_zero(PulldownPattern::Advanced);
+    let cadence: Vec<_> = (5u32..10)
+        .map(|n| pulldown.video_cadence(Timecode::<FrameRate2997>::from(n)))
+        .collect();
+    assert_eq!(cadence, vec![(A, A), (B, B), (B, C), (C, C), (D, D)]);
+
+    let split: Vec<_> = (0u32..5)
+        .filter(|&n| pulldown.is_split_frame(Timecode::from(n)))
+        .collect();
+    assert_eq!(split, vec![2]);
+}
+
+#[test]
+fn pulldown_film_to_video_and_back() {
+    use video_timecode::pulldown::*;
+
+    for &pattern in &[PulldownPattern::Standard, PulldownPattern::Advanced] {
+        let pulldown = pulldown::Pulldown::new(
+            pattern,
+            Timecode::<FrameRate2398>::new(1, 0, 0, 0).unwrap(),
+            Timecode::<FrameRate2997>::new(0, 59, 56, 10).unwrap(),
+        );
+
+        for n in 86380u32..86420 {
+            let film = Timecode::<FrameRate2398>::from(n);
+            let (video, field) = pulldown.film_to_video(film);
+            let (first, second) = pulldown.video_to_film(video);
+            match field {
+                Field::First => assert_eq!(first, film),
+                Field::Second => assert_eq!(second, film),
+            }
+            let count = pulldown.film_field_count(film);
+            assert!(count == 2 || count == 3);
+        }
+    }
+}
+
+#[test]
+fn pulldown_before_reference() {
+    use video_timecode::pulldown::*;
+
+    let pulldown = pulldown::Pulldown::new(
+        PulldownPattern::Standard,
+        Timecode::<FrameRate2398>::new(1, 0, 0, 0).unwrap(),
+        Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap(),
+    );
+
+    let film = Timecode::<FrameRate2398>::new(0, 59, 59, 23).unwrap();
+    assert_eq!(pulldown.film_cadence(film), CadenceFrame::D);
+    assert_eq!(pulldown.film_field_count(film), 3);
+    let (video, field) = pulldown.film_to_video(film);
+    assert_eq!(video.to_string(), "00:59:59;28");
+    assert_eq!(field, Field::Second);
+}
+
+#[test]
+fn pulldown_across_midnight() {
+    use video_timecode::pulldown::*;
+
+    let pulldown = pulldown::Pulldown::new(
+        PulldownPattern::Standard,
+        Timecode::<FrameRate2398>::new(23, 59, 59, 20).unwrap(),
+        Timecode::<FrameRate2997>::new(23, 59, 59, 25).unwrap(),
+    );
+
+    let film = Timecode::<FrameRate2398>::new(0, 0, 0, 0).unwrap();
+    assert_eq!(pulldown.film_cadence(film), CadenceFrame::A);
+    let (video, field) = pulldown.film_to_video(film);
+    assert_eq!(video.to_string(), "00:00:00;00");
+    assert_eq!(field, Field::First);
+    assert_eq!(pulldown.video_to_film(video), (film, film));
+
+    let film = Timecode::<FrameRate2398>::new(0, 0, 0, 1).unwrap();
+    let (video, field) = pulldown.film_to_video(film);
+    assert_eq!(video.to_string(), "00:00:00;01");
+    assert_eq!(field, Field::First);
+}
+
+// Test timecode ranges
+
+fn range_25(start: u32, end: u32) -> TimecodeRange<FrameRate25> {
+    TimecodeRange::new(Timecode::from(start), Timecode::from(end))
+}
+
+const DAY_25: u32 = 2160000;
+
+#[test]
+fn range_length_and_contains() {
+    let range = range_25(100, 200);
+    assert_eq!(range.length().frames(), 100);
+    assert!(range.contains(Timecode::from(100u32)));
+    assert!(range.contains(Timecode::from(199u32)));
+    assert!(!range.contains(Timecode::from(200u32)));
+    assert!(!range.contains(Timecode::from(99u32)));
+    assert!(range_25(100, 100).is_empty());
+}
+
+#[test]
+fn range_spanning_midnight() {
+    let range = range_25(DAY_25 - 10, 10);
+    assert_eq!(range.length().frames(), 20);
+    assert!(range.contains(Timecode::from(0u32)));
+    assert!(range.contains(Timecode::from(DAY_25 - 1)));
+    assert!(!range.contains(Timecode::from(10u32)));
+    assert_eq!(
+        range.intersection(&range_25(5, 100)),
+        Some(range_25(5, 10))
+    );
+    assert_eq!(
+        range.union(&range_25(10, 100)),
+        Some(range_25(DAY_25 - 10, 100))
+    );
+    assert_eq!(
+        range.split_at(Timecode::from(0u32)),
+        Some((range_25(DAY_25 - 10, 0), range_25(0, 10)))
+    );
+}
+
+#[test]
+fn range_with_length() {
+    let start = Timecode::<FrameRate25>::from(DAY_25 - 1);
+    assert!(
+        TimecodeRange::with_length(start, TimecodeDuration::from_frames(-1))
+            .is_err()
+    );
+    assert!(TimecodeRange::with_length(
+        start,
+        TimecodeDuration::from_frames(DAY_25 as i64 + 1)
+    )
+    .is_err());
+
+    let day = TimecodeRange::with_length(
+        start,
+        TimecodeDuration::from_frames(DAY_25 as i64),
+    )
+    .unwrap();
+    assert_eq!(day.end(), start);
+    assert!(day.contains(Timecode::from(12345u32)));
+}
+
+#[test]
+fn range_intersection_and_overlap() {
+    let range = range_25(100, 200);
+    assert_eq!(
+        range.intersection(&range_25(50, 150)),
+        Some(range_25(100, 150))
+    );
+    assert_eq!(
+        range.intersection(&range_25(120, 130)),
+        Some(range_25(120, 130))
+    );
+    assert_eq!(range.intersection(&range_25(0, 100)), None);
+    assert!(!range.overlaps(&range_25(200, 300)));
+    assert!(!range.overlaps(&range_25(150, 150)));
+}
+
+#[test]
+fn range_union() {
+    let range = range_25(100, 200);
+    assert_eq!(range.union(&range_25(50, 100)), Some(range_25(50, 200)));
+    assert_eq!(range.union(&range_25(150, 300)), Some(range_25(100, 300)));
+    assert_eq!(range.union(&range_25(0, 99)), None);
+
+    let merged = range_25(0, DAY_25 / 2)
+        .union(&range_25(DAY_25 / 4, 1))
+        .unwrap();
+    assert_eq!(merged.length().frames(), DAY_25 as i64);
+}
+
+#[test]
+fn range_split_at() {
+    let range = range_25(100, 200);
+    assert_eq!(range.split_at(Timecode::from(200u32)), None);
+    assert_eq!(
+        range.split_at(Timecode::from(100u32)),
+        Some((range_25(100, 100), range_25(100, 200)))
+    );
+}
+
+#[test]
+fn range_iterate() {
+    let range = range_25(DAY_25 - 2, 2);
+    let frames: Vec<u32> = range.iter().map(|tc| tc.frame_number).collect();
+    assert_eq!(frames, vec![DAY_25 - 2, DAY_25 - 1, 0, 1]);
+
+    let reversed: Vec<u32> =
+        range.into_iter().rev().map(|tc| tc.frame_number).collect();
+    assert_eq!(reversed, vec![1, 0, DAY_25 - 1, DAY_25 - 2]);
+    assert_eq!(range.iter().len(), 4);
+}
+
+// Test frame iterators
+
+macro_rules! test_frames_match_frame_numbers {
+    ($name:ident, $frame_rate:ident) => {
+        #[test]
+        fn $name() {
+            let max_frames = $frame_rate::MAX_FRAMES;
+
+            let start = Timecode::<$frame_rate>::from(max_frames - 50000);
+            for (i, tc) in start.frames().take(100000).enumerate() {
+                let expected = Timecode::<$frame_rate>::from(
+                    (max_frames - 50000 + i as u32) % max_frames,
+                );
+                assert_eq!(tc, expected);
+            }
+
+            let start = Timecode::<$frame_rate>::from(50000u32);
+            for (i, tc) in start.frames_rev().take(100000).enumerate() {
+                let expected = Timecode::<$frame_rate>::from(
+                    (max_frames + 50000 - i as u32) % max_frames,
+                );
+                assert_eq!(tc, expected);
+            }
+        }
+    };
+}
+
+test_frames_match_frame_numbers!(frames_24, FrameRate24);
+test_frames_match_frame_numbers!(frames_2997, FrameRate2997);
+test_frames_match_frame_numbers!(frames_5994, FrameRate5994);
+
+#[test]
+fn step_by_duration_backward() {
+    let tc = Timecode::<FrameRate25>::new(0, 0, 1, 0).unwrap();
+    let frames: Vec<u32> = tc
+        .step_by_duration(TimecodeDuration::from_frames(-10))
+        .take(4)
+        .map(|tc| tc.frame_number)
+        .collect();
+    assert_eq!(frames, vec![25, 15, 5, 2159995]);
+}
+
+#[test]
+fn range_iterate_df() {
+    let start = Timecode::<FrameRate2997>::new(0, 9, 59, 28).unwrap();
+    let end = Timecode::<FrameRate2997>::new(0, 10, 0, 2).unwrap();
+    let frames: Vec<String> = TimecodeRange::new(start, end)
+        .iter()
+        .rev()
+        .map(|tc| tc.to_string())
+        .collect();
+    assert_eq!(
+        frames,
+        vec!["00:10:00;01", "00:10:00;00", "00:09:59;29", "00:09:59;28"]
+    );
+}
+
+// Test non drop frame 29.97 and 59.94
+
+test_factories!(
+    frame_number_2997_ndf_with_minutes,
+    0,
+    23,
+    30,
+    15,
+    FrameRate2997Ndf,
+    42315
+);
+test_factories!(
+    frame_number_5994_ndf_with_hours,
+    23,
+    59,
+    59,
+    59,
+    FrameRate5994Ndf,
+    5183999
+);
+
+#[test]
+fn ndf_2997_has_no_dropped_frames() {
+    let tc = Timecode::<FrameRate2997Ndf>::new(0, 1, 0, 0).unwrap();
+    assert_eq!(tc.frame_number, 1800);
+    assert_eq!(tc.to_string(), "00:01:00:00");
+}
+
+#[test]
+fn ndf_2997_keeps_fractional_rate() {
+    let tc = Timecode::<FrameRate2997Ndf>::new(0, 0, 1, 0).unwrap();
+    assert_eq!(tc.to_duration(), std::time::Duration::from_millis(1001));
+}
+
+#[test]
+fn parse_ndf_5994() {
+    let tc = Timecode::<FrameRate5994Ndf>::from_str("00:00:20:10").unwrap();
+    assert_eq!(tc.frame_number, 1210);
+
+    match Timecode::<FrameRate5994Ndf>::from_str("00:00:20;10") {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn relabel_df_and_ndf() {
+    let df = Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap();
+    let ndf = Timecode::<FrameRate2997Ndf>::from(df);
+    assert_eq!(ndf.to_string(), "00:59:56:12");
+    assert_eq!(Timecode::<FrameRate2997>::from(ndf), df);
+
+    let df = Timecode::<FrameRate5994>::new(0, 10, 0, 0).unwrap();
+    let ndf = Timecode::<FrameRate5994Ndf>::from(df);
+    assert_eq!(ndf.to_string(), "00:09:59:24");
+    assert_eq!(Timecode::<FrameRate5994>::from(ndf), df);
+
+    let df = Timecode::<FrameRate11988>::new(0, 10, 0, 0).unwrap();
+    let ndf = Timecode::<FrameRate11988Ndf>::from(df);
+    assert_eq!(ndf.to_string(), "00:09:59:048");
+    assert_eq!(Timecode::<FrameRate11988>::from(ndf), df);
+}
+
+#[test]
+fn any_frame_rate_ndf() {
+    assert_eq!(
+        AnyFrameRate::new(30000, 1001, false),
+        Some(AnyFrameRate::Fps2997Ndf)
+    );
+    assert_eq!(
+        AnyFrameRate::from_str("59.94 NDF").unwrap(),
+        AnyFrameRate::Fps5994Ndf
+    );
+    assert_eq!(
+        AnyFrameRate::from_str("59.94").unwrap(),
+        AnyFrameRate::Fps5994
+    );
+    assert!(AnyFrameRate::from_str("25DF").is_err());
+    let tc = AnyTimecode::from_str("00:01:00:00@29.97NDF").unwrap();
+    assert_eq!(tc.frame_number, 1800);
+}
+
+// Test high frame rates
+
+test_factories!(frame_number_48_with_hours, 5, 15, 25, 12, FrameRate48, 908412);
+test_factories!(
+    frame_number_4795_with_hours,
+    5,
+    15,
+    25,
+    47,
+    FrameRate4795,
+    908447
+);
+test_factories!(
+    frame_number_72_with_hours,
+    23,
+    59,
+    59,
+    71,
+    FrameRate72,
+    6220799
+);
+test_factories!(frame_number_96_with_seconds, 0, 0, 40, 95, FrameRate96, 3935);
+test_factories!(
+    frame_number_100_with_minutes,
+    0,
+    23,
+    30,
+    99,
+    FrameRate100,
+    141099
+);
+test_factories!(
+    frame_number_120_with_hours,
+    23,
+    59,
+    59,
+    119,
+    FrameRate120,
+    10367999
+);
+test_factories!(
+    frame_number_11988_ndf_with_minutes,
+    0,
+    1,
+    0,
+    0,
+    FrameRate11988Ndf,
+    7200
+);
+test_factories!(
+    frame_number_11988_first_minute,
+    0,
+    1,
+    0,
+    8,
+    FrameRate11988,
+    7200
+);
+test_factories!(
+    frame_number_11988_with_hours,
+    23,
+    59,
+    59,
+    119,
+    FrameRate11988,
+    10357631
+);
+
+test_frames_match_frame_numbers!(frames_11988, FrameRate11988);
+
+#[test]
+fn dropped_timecode_11988() {
+    match Timecode::<FrameRate11988>::new(10, 1, 0, 7) {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidTimecode,
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn frame_equal_to_fps_is_invalid() {
+    assert!(Timecode::<FrameRate24>::new(0, 0, 0, 24).is_err());
+    assert!(Timecode::<FrameRate120>::new(0, 0, 0, 120).is_err());
+    assert!(Timecode::<FrameRate120>::new(0, 0, 0, 119).is_ok());
+}
+
+// The last frame of a second, and the frame number after the last frame of
+// a day, used to be accepted.
+#[test]
+fn frame_past_last_frame_of_second_is_invalid() {
+    assert!(Timecode::<FrameRate25>::new(0, 0, 0, 25).is_err());
+    assert!(Timecode::<FrameRate2997>::new(0, 0, 59, 30).is_err());
+    assert!(Timecode::<FrameRate5994>::new(23, 59, 59, 60).is_err());
+}
+
+#[test]
+#[should_panic(expected = "Frame rate only supports up to 2073600 frames.")]
+fn time_code_for_max_frames_panics() {
+    FrameRate24::calculate_time_code(FrameRate24::MAX_FRAMES);
+}
+
+#[test]
+fn parse_and_format_three_digit_frames() {
+    let tc = Timecode::<FrameRate120>::from_str("01:00:00:119").unwrap();
+    assert_eq!(tc.frame_number, 432119);
+    assert_eq!(tc.to_string(), "01:00:00:119");
+
+    let tc = Timecode::<FrameRate11988>::from_str("00:01:00;008").unwrap();
+    assert_eq!(tc.to_string(), "00:01:00;008");
+    assert_eq!(tc, Timecode::from_str("00:01:00;08").unwrap());
+
+    assert!(Timecode::<FrameRate100>::from_str("00:00:00:099").is_err());
+    assert_eq!(
+        Timecode::<FrameRate100>::new(0, 0, 0, 99).unwrap().to_string(),
+        "00:00:00:99"
+    );
+}
+
+#[test]
+fn any_frame_rate_high_frame_rates() {
+    assert_eq!(
+        AnyFrameRate::new(120000, 1001, true),
+        Some(AnyFrameRate::Fps11988)
+    );
+    assert_eq!(
+        AnyFrameRate::from_str("47.95").unwrap(),
+        AnyFrameRate::Fps4795
+    );
+    let tc = AnyTimecode::from_str("10:00:00:100@119.88NDF").unwrap();
+    assert_eq!(format!("{:#}", tc), "10:00:00:100@119.88NDF");
+}
+
+// Test frame pairs
+
+#[test]
+fn frame_pairs_round_trip() {
+    for frame_number in 0..200_000 {
+        let tc = Timecode::<FrameRate5994>::from(frame_number);
+        let pair = tc.frame_pair();
+        assert_eq!(pair.timecode.frame_number, frame_number / 2);
+        assert_eq!(pair.field_flag, frame_number % 2 == 1);
+        assert_eq!(pair.timecode.hour, tc.hour);
+        assert_eq!(pair.timecode.minute, tc.minute);
+        assert_eq!(pair.timecode.second, tc.second);
+        assert_eq!(pair.timecode.frame, tc.frame / 2);
+        assert_eq!(Timecode::from_frame_pair(pair), tc);
+    }
+}
+
+#[test]
+fn frame_pair_60() {
+    let tc = Timecode::<FrameRate60>::new(23, 59, 59, 58).unwrap();
+    let pair = tc.frame_pair();
+    assert_eq!(pair.timecode, Timecode::new(23, 59, 59, 29).unwrap());
+    assert!(!pair.field_flag);
+
+    let pair = FramePair {
+        timecode: Timecode::<FrameRate30>::new(1, 2, 3, 4).unwrap(),
+        field_flag: true,
+    };
+    assert_eq!(
+        Timecode::<FrameRate60>::from_frame_pair(pair).to_string(),
+        "01:02:03:09"
+    );
+}
+
+// Test extended timecodes
+
+#[test]
+fn extended_timecode_frame_numbers_continue_past_a_day() {
+    for &frame_number in &[0u64, 2589407, 2589408, 2589409, 100_000_000] {
+        let tc = ExtendedTimecode::<FrameRate2997>::from(frame_number);
+        let day = Timecode::<FrameRate2997>::from(frame_number % 2589408);
+        assert_eq!(tc.frame_number, frame_number);
+        assert_eq!(tc.hour, frame_number / 2589408 * 24 + day.hour as u64);
+        assert_eq!(tc.minute, day.minute);
+        assert_eq!(tc.second, day.second);
+        assert_eq!(tc.frame, day.frame);
+        assert_eq!(
+            ExtendedTimecode::new(tc.hour, tc.minute, tc.second, tc.frame)
+                .unwrap(),
+            tc
+        );
+    }
+}
+
+#[test]
+fn extended_timecode_parse_and_format() {
+    let tc = ExtendedTimecode::<FrameRate2997>::from_str("123:45:01;02")
+        .unwrap();
+    assert_eq!(tc.hour, 123);
+    assert_eq!(tc.to_string(), "123:45:01;02");
+    assert_eq!(format!("{:#}", tc), "5d 03:45:01;02");
+    assert_eq!(
+        ExtendedTimecode::<FrameRate2997>::from_str("5d 03:45:01;02").unwrap(),
+        tc
+    );
+
+    let tc = ExtendedTimecode::<FrameRate25>::from_str("01:00:00:00").unwrap();
+    assert_eq!(format!("{:#}", tc), "01:00:00:00");
+
+    for s in &[
+        "1:00:00:00",
+        "d 01:00:00:00",
+        "1d 100:00:00:00",
+        "100:00:00;00",
+        "+100:00:00:00",
+        "30:60:00:00",
+    ] {
+        match ExtendedTimecode::<FrameRate25>::from_str(s) {
+            Err(TimecodeError {
+                kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+                ..
+            }) => {}
+            _ => panic!("{}", s),
+        }
+    }
+
+    for s in &["1d 24:00:00:00", "30:00:00:25"] {
+        match ExtendedTimecode::<FrameRate25>::from_str(s) {
+            Err(TimecodeError {
+                kind: video_timecode::TimecodeErrorKind::InvalidTimecode,
+                ..
+            }) => {}
+            _ => panic!("{}", s),
+        }
+    }
+}
+
+#[test]
+fn extended_timecode_arithmetic() {
+    let tc = ExtendedTimecode::<FrameRate25>::new(23, 59, 59, 24).unwrap();
+    assert_eq!((tc + 1u8).to_string(), "24:00:00:00");
+    assert_eq!(tc + 1u8 - 1u8, tc);
+    assert_eq!(tc + tc, ExtendedTimecode::from(tc.frame_number * 2));
+
+    let length = TimecodeDuration::<FrameRate25>::from_frames(25 * 3600);
+    let later = tc + length;
+    assert_eq!(later.to_string(), "24:59:59:24");
+    assert_eq!(later - tc, length);
+    assert_eq!(later - length, tc);
+
+    assert_eq!(ExtendedTimecode::<FrameRate25>::from(0u8).checked_sub(1), None);
+    assert_eq!(tc.checked_add(1).unwrap().hour, 24);
+}
+
+#[test]
+#[should_panic]
+fn extended_timecode_subtract_below_zero() {
+    let _ = ExtendedTimecode::<FrameRate25>::from(0u8) - 1;
+}
+
+#[test]
+fn extended_timecode_to_timecode() {
+    use std::convert::TryFrom;
+
+    let tc = Timecode::<FrameRate24>::new(12, 0, 0, 0).unwrap();
+    let extended = ExtendedTimecode::from(tc);
+    assert_eq!(extended.frame_number, tc.frame_number as u64);
+    assert_eq!(Timecode::try_from(extended).unwrap(), tc);
+
+    let extended = extended + length_of_day::<FrameRate24>();
+    assert!(Timecode::try_from(extended).is_err());
+    assert_eq!(extended.time_of_day(), tc);
+}
+
+fn length_of_day<T: FrameRate>() -> TimecodeDuration<T> {
+    let tc = ExtendedTimecode::<T>::new(24, 0, 0, 0).unwrap();
+    TimecodeDuration::from_frames(tc.frame_number as i64)
+}
+
+// Test signed offsets
+
+#[test]
+fn parse_signed_durations() {
+    let parse = |s| TimecodeDuration::<FrameRate2997>::from_str(s).unwrap();
+
+    assert_eq!(parse("-00:00:01;12").frames(), -42);
+    assert_eq!(parse("+00:00:01;12").frames(), 42);
+    assert_eq!(parse("00:00:01;12").frames(), 42);
+    assert_eq!(parse("-00:01:00;02").frames(), -1800);
+    assert_eq!(parse("-25:00:00;00").to_string(), "-25:00:00;00");
+
+    for s in &["--00:00:01;12", "+-00:00:01;12", "- 00:00:01;12", "-"] {
+        assert!(TimecodeDuration::<FrameRate2997>::from_str(s).is_err());
+    }
+
+    match TimecodeDuration::<FrameRate25>::from_str("-00:00:01;12") {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn format_signed_durations() {
+    let duration = TimecodeDuration::<FrameRate24>::from(-36i8);
+    assert_eq!(duration.to_string(), "-00:00:01:12");
+    assert_eq!(format!("{:+}", duration), "-00:00:01:12");
+    assert_eq!(format!("{:+}", -duration), "+00:00:01:12");
+    assert_eq!(format!("{:+}", duration - duration), "+00:00:00:00");
+
+    for s in &["-00:00:01:12", "+10:00:00:00", "-100:00:00:01"] {
+        let duration = TimecodeDuration::<FrameRate24>::from_str(s).unwrap();
+        assert_eq!(&format!("{:+}", duration), s);
+    }
+}
+
+#[test]
+fn signed_offsets_move_timecodes() {
+    let tc = Timecode::<FrameRate24>::new(0, 0, 0, 12).unwrap();
+    let offset = TimecodeDuration::from(-24i64);
+
+    assert_eq!(tc.checked_add(offset.frames()), None);
+    assert_eq!((tc + offset).to_string(), "23:59:59:12");
+    assert_eq!(
+        (ExtendedTimecode::from(tc) - offset).to_string(),
+        "00:00:01:12"
+    );
+}
+
+// Test film feet and frames
+
+#[test]
+fn feet_frames_round_trip() {
+    use video_timecode::film::*;
+
+    for &(format, frames_per_three_feet) in &[
+        (FilmFormat::ThirtyFive4Perf, 48),
+        (FilmFormat::ThirtyFive3Perf, 64),
+        (FilmFormat::ThirtyFive2Perf, 96),
+        (FilmFormat::Sixteen, 120),
+    ] {
+        let mut previous = FeetFrames::from_frame_number(0, format);
+        assert_eq!(previous, FeetFrames { feet: 0, frames: 0 });
+
+        for frame_number in 1..1000u64 {
+            let position = FeetFrames::from_frame_number(frame_number, format);
+            assert_eq!(position.to_frame_number(format).unwrap(), frame_number);
+            assert!(position > previous);
+            assert_eq!(
+                position.feet,
+                frame_number / frames_per_three_feet * 3
+                    + FeetFrames::from_frame_number(
+                        frame_number % frames_per_three_feet,
+                        format
+                    )
+                    .feet
+            );
+            previous = position;
+        }
+    }
+}
+
+#[test]
+fn feet_frames_3_perf_cadence() {
+    use video_timecode::film::*;
+
+    let format = FilmFormat::ThirtyFive3Perf;
+    let invalid = |feet, frames| {
+        FeetFrames { feet, frames }.to_frame_number(format).is_err()
+    };
+
+    assert!(!invalid(0, 21));
+    assert!(invalid(0, 22));
+    assert!(!invalid(1, 20));
+    assert!(invalid(1, 21));
+    assert!(!invalid(2, 20));
+    assert!(invalid(2, 21));
+    assert!(!invalid(3, 21));
+    assert!(invalid(u64::MAX, 0));
+}
+
+#[test]
+fn feet_frames_parse_and_format() {
+    use video_timecode::film::*;
+
+    let position = FeetFrames::from_str("1234+07").unwrap();
+    assert_eq!(position, FeetFrames { feet: 1234, frames: 7 });
+    assert_eq!(position.to_string(), "1234+07");
+    assert_eq!(FeetFrames::from_str("0+7").unwrap().to_string(), "0+07");
+
+    for s in &["1234", "+07", "1234+", "1234+123", "-1+00", "12 34+00"] {
+        match FeetFrames::from_str(s) {
+            Err(TimecodeError {
+                kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+                ..
+            }) => {}
+            _ => panic!("{}", s),
+        }
+    }
+}
+
+#[test]
+fn feet_frames_timecodes() {
+    use video_timecode::film::*;
+
+    let tc = Timecode::<FrameRate24>::new(1, 0, 0, 0).unwrap();
+    let position = tc.to_feet_frames(FilmFormat::Sixteen);
+    assert_eq!(position.to_string(), "2160+00");
+    assert_eq!(
+        Timecode::<FrameRate24>::from_feet_frames(
+            position,
+            FilmFormat::Sixteen
+        )
+        .unwrap(),
+        tc
+    );
+
+    let end = FeetFrames::from_frame_number(2073600, FilmFormat::Sixteen);
+    assert!(
+        Timecode::<FrameRate24>::from_feet_frames(end, FilmFormat::Sixteen)
+            .is_err()
+    );
+}
+
+#[test]
+fn keycode_parse_and_format() {
+    use video_timecode::film::*;
+
+    let keycode = KeyCode::from_str("KU 22 9012 1234+12").unwrap();
+    assert_eq!(keycode.manufacturer, 'K');
+    assert_eq!(keycode.film_type, 'U');
+    assert_eq!(keycode.prefix, 229012);
+    assert_eq!(keycode.feet_frames, FeetFrames { feet: 1234, frames: 12 });
+    assert_eq!(keycode.perforation_offset, 0);
+    assert_eq!(keycode.to_string(), "KU 22 9012 1234+12");
+
+    let keycode = KeyCode::from_str("EB 01 0042 0005+03.2").unwrap();
+    assert_eq!(keycode.perforation_offset, 2);
+    assert_eq!(keycode.to_string(), "EB 01 0042 0005+03.2");
+
+    for s in &[
+        "KU 22 9012 1234",
+        "KU22 9012 1234+12",
+        "KUU 22 9012 1234+12",
+        "KU 22 9012 123+12",
+        "KU 22 9012 1234+12.",
+        "KU 22 9012 1234+12 ",
+        "1U 22 9012 1234+12",
+    ] {
+        assert!(KeyCode::from_str(s).is_err(), "{}", s);
+    }
+}
+
+#[test]
+fn keycode_offsets() {
+    use video_timecode::film::*;
+
+    let format = FilmFormat::ThirtyFive3Perf;
+    let keycode = KeyCode::from_str("KU 22 9012 1000+00").unwrap();
+
+    let later = keycode.offset(22, format).unwrap();
+    assert_eq!(later.to_string(), "KU 22 9012 1001+00.2");
+    assert_eq!(later.frames_since(&keycode, format), Some(22));
+    assert_eq!(keycode.frames_since(&later, format), Some(-22));
+    assert_eq!(keycode.offset(64, format).unwrap().to_string(),
+               "KU 22 9012 1003+00");
+
+    let shifted = KeyCode {
+        perforation_offset: 1,
+        ..keycode
+    };
+    assert_eq!(shifted.frames_since(&keycode, format), None);
+
+    let other_roll = KeyCode {
+        prefix: 229013,
+        ..keycode
+    };
+    assert_eq!(other_roll.frames_since(&keycode, format), None);
+
+    assert_eq!(keycode.offset(-21334, format), None);
+    assert!(keycode.offset(-21333, format).is_some());
+}
+
+// Test audio sample positions
+
+macro_rules! test_sample_round_trip {
+    ($name:ident, $frame_rate:ident, $sample_rate:expr) => {
+        #[test]
+        fn $name() {
+            let starts = [
+                0,
+                1,
+                2,
+                3,
+                4,
+                5,
+                1000,
+                1799,
+                1800,
+                17982,
+                <$frame_rate as FrameRate>::MAX_FRAMES - 1,
+            ];
+
+            for &frame_number in starts.iter() {
+                let tc = Timecode::<$frame_rate>::from(frame_number);
+                let start = tc.to_sample($sample_rate);
+                let length = tc.samples_in_frame($sample_rate) as u64;
+
+                assert_eq!(
+                    Timecode::<$frame_rate>::from_sample(start, $sample_rate),
+                    (tc, 0)
+                );
+                assert_eq!(
+                    Timecode::<$frame_rate>::from_sample(
+                        start + length - 1,
+                        $sample_rate
+                    ),
+                    (tc, length as u32 - 1)
+                );
+                assert_eq!(
+                    Timecode::<$frame_rate>::from_sample(
+                        start + length,
+                        $sample_rate
+                    )
+                    .1,
+                    0
+                );
+            }
+        }
+    };
+}
+
+test_sample_round_trip!(samples_2997_48k, FrameRate2997, 48000);
+test_sample_round_trip!(samples_2997_44k, FrameRate2997, 44100);
+test_sample_round_trip!(samples_2398_48k, FrameRate2398, 48000);
+test_sample_round_trip!(samples_5994_96k, FrameRate5994, 96000);
+test_sample_round_trip!(samples_25_192k, FrameRate25, 192000);
+test_sample_round_trip!(samples_24_44k, FrameRate24, 44100);
+
+#[test]
+fn sample_cadence_2997() {
+    let tc = Timecode::<FrameRate2997>::from(1_000_000);
+    let lengths: Vec<u32> = tc
+        .frames()
+        .take(10)
+        .map(|tc| tc.samples_in_frame(48000))
+        .collect();
+    assert_eq!(
+        lengths,
+        [1602, 1601, 1602, 1601, 1602, 1602, 1601, 1602, 1601, 1602]
+    );
+
+    // Five frames are exactly 8008 samples.
+    assert_eq!(tc.to_sample(48000) % 8008, 0);
+    assert_eq!((tc + 5u8).to_sample(48000) - tc.to_sample(48000), 8008);
+}
+
+#[test]
+fn sample_positions_are_exact() {
+    let tc = Timecode::<FrameRate2398>::new(10, 0, 0, 0).unwrap();
+    assert_eq!(tc.to_sample(48000), 36000 * 48048);
+    assert_eq!(tc.samples_in_frame(48000), 2002);
+
+    let tc = Timecode::<FrameRate24>::new(23, 59, 59, 22).unwrap();
+    assert_eq!(tc.to_sample(44100), 2073598 * 44100 / 24);
+    assert_eq!(tc.samples_in_frame(44100), 1838);
+    assert_eq!((tc + 1u8).samples_in_frame(44100), 1837);
+
+    let (tc, offset) =
+        Timecode::<FrameRate25>::from_sample(86400 * 48000, 48000);
+    assert_eq!(tc.frame_number, 0);
+    assert_eq!(offset, 0);
+}
+
+// Test subframe timecodes
+
+#[test]
+fn subframe_timecode_parse_and_format() {
+    let tc = SubframeTimecode::<FrameRate2997>::parse(100, "01:00:00;00.45")
+        .unwrap();
+    assert_eq!(tc.timecode, Timecode::new(1, 0, 0, 0).unwrap());
+    assert_eq!(tc.subframe, 45);
+    assert_eq!(tc.subframes_per_frame(), 100);
+    assert_eq!(tc.to_string(), "01:00:00;00.45");
+
+    let tc = SubframeTimecode::<FrameRate24>::parse(80, "01:00:00:00").unwrap();
+    assert_eq!(tc.to_string(), "01:00:00:00.00");
+
+    match SubframeTimecode::<FrameRate24>::parse(80, "01:00:00:00.80") {
+        Err(TimecodeError {
+            kind: video_timecode::TimecodeErrorKind::InvalidTimecode,
+            ..
+        }) => {}
+        _ => panic!(),
+    }
+
+    for s in &["01:00:00:00.", "01:00:00:00.-1", "01:00:00:00.1234"] {
+        match SubframeTimecode::<FrameRate24>::parse(80, s) {
+            Err(TimecodeError {
+                kind: video_timecode::TimecodeErrorKind::InvalidFormat,
+                ..
+            }) => {}
+            _ => panic!("{}", s),
+        }
+    }
+
+    assert!(SubframeTimecode::new(tc.timecode, 0, 0).is_err());
+}
+
+#[test]
+fn subframe_timecode_arithmetic() {
+    let tc = SubframeTimecode::<FrameRate2997>::parse(80, "00:00:59;29.79")
+        .unwrap();
+    assert_eq!(tc.wrapping_add_subframes(1).to_string(), "00:01:00;02.00");
+    assert_eq!(
+        tc.wrapping_add_subframes(1).wrapping_sub_subframes(1),
+        tc
+    );
+
+    let start = SubframeTimecode::<FrameRate2997>::parse(80, "00:00:00;00.00")
+        .unwrap();
+    assert_eq!(
+        start.wrapping_sub_subframes(1).to_string(),
+        "23:59:59;29.79"
+    );
+
+    let length = TimecodeDuration::from_frames(2);
+    assert_eq!((tc + length).to_string(), "00:01:00;03.79");
+    assert_eq!(tc + length - length, tc);
+    assert_eq!((tc + tc).total_subframes(), tc.total_subframes() * 2);
+}
+
+#[test]
+#[should_panic]
+fn subframe_timecode_add_different_subframes() {
+    let tc1 = SubframeTimecode::<FrameRate25>::parse(80, "00:00:00:00.10");
+    let tc2 = SubframeTimecode::<FrameRate25>::parse(100, "00:00:00:00.10");
+    let _ = tc1.unwrap() + tc2.unwrap();
+}
+
+#[test]
+fn subframe_timecode_real_time() {
+    use std::time::Duration;
+
+    let tc = SubframeTimecode::<FrameRate2398>::parse(100, "00:00:01:00.00")
+        .unwrap();
+    assert_eq!(tc.to_duration(), Duration::from_millis(1001));
+    assert_eq!(tc.to_seconds(), 1.001);
+
+    for subframes in 0..1000 {
+        let tc = start_2398().wrapping_add_subframes(subframes);
+        assert_eq!(
+            SubframeTimecode::from_duration(
+                tc.to_duration(),
+                100,
+                Rounding::Nearest
+            ),
+            tc
+        );
+    }
+}
+
+fn start_2398() -> SubframeTimecode<FrameRate2398> {
+    SubframeTimecode::parse(100, "10:00:00:00").unwrap()
+}
+
+// Test timecode formats
+
+#[test]
+fn timecode_format_separators() {
+    let tc = Timecode::<FrameRate2997>::new(1, 2, 3, 4).unwrap();
+    let display = |format: TimecodeFormat| format.display(tc).to_string();
+
+    assert_eq!(display(TimecodeFormat::new()), "01:02:03;04");
+    assert_eq!(display(TimecodeFormat::default()), tc.to_string());
+    assert_eq!(display(TimecodeFormat::new().separator(';')), "01;02;03;04");
+    assert_eq!(display(TimecodeFormat::new().separator('.')), "01.02.03.04");
+    assert_eq!(display(TimecodeFormat::new().separator(':')), "01:02:03:04");
+    assert_eq!(
+        display(TimecodeFormat::new().separator('.').frame_separator(',')),
+        "01.02.03,04"
+    );
+    assert_eq!(
+        display(TimecodeFormat::new().frame_separator('.')),
+        "01:02:03.04"
+    );
+    assert_eq!(
+        display(TimecodeFormat::new().without_separators()),
+        "01020304"
+    );
+
+    let tc = Timecode::<FrameRate25>::new(1, 2, 3, 4).unwrap();
+    assert_eq!(
+        TimecodeFormat::new().display(tc).to_string(),
+        "01:02:03:04"
+    );
+}
+
+#[test]
+fn timecode_format_layouts() {
+    let tc = Timecode::<FrameRate120>::new(0, 2, 3, 4).unwrap();
+    let display = |format: TimecodeFormat| format.display(tc).to_string();
+
+    assert_eq!(display(TimecodeFormat::new().omit_zero_hours()), "02:03:004");
+    assert_eq!(
+        display(TimecodeFormat::new().omit_zero_hours().without_separators()),
+        "0203004"
+    );
+    assert_eq!(display(TimecodeFormat::new().frame_count()), "14764");
+    assert_eq!(display(TimecodeFormat::new().seconds(2)), "123.03");
+    assert_eq!(display(TimecodeFormat::new().seconds(0)), "123");
+    assert_eq!(display(TimecodeFormat::new().seconds(12)), "123.033333333");
+    assert_eq!(
+        display(TimecodeFormat::new().frame_count().rate_suffix()),
+        "14764@120"
+    );
+
+    let tc = Timecode::<FrameRate2398>::new(1, 0, 0, 0).unwrap();
+    assert_eq!(
+        TimecodeFormat::new().omit_zero_hours().display(tc).to_string(),
+        "01:00:00:00"
+    );
+    assert_eq!(
+        TimecodeFormat::new().seconds(3).display(tc).to_string(),
+        "3603.600"
+    );
+}
+
+#[test]
+fn timecode_format_flags() {
+    let tc = Timecode::<FrameRate2997>::new(10, 0, 0, 0).unwrap();
+    let format = TimecodeFormat::new().frame_count();
+
+    assert_eq!(format!("{:>10}", format.display(tc)), "   1078920");
+    assert_eq!(format!("{:<10}|", format.display(tc)), "1078920   |");
+    assert_eq!(format!("{:#}", format.display(tc)), "1078920@29.97DF");
+    assert_eq!(format!("{:>22}", tc), "           10:00:00;00");
+    assert_eq!(format!("{:#}", tc), "10:00:00;00@29.97DF");
+
+    let any = AnyTimecode::try_from(tc).unwrap();
+    assert_eq!(format.display(any).to_string(), "1078920");
+}
+
+#[test]
+fn timecode_format_into_buffer() {
+    let tc = Timecode::<FrameRate5994>::new(23, 59, 59, 59).unwrap();
+    let format = TimecodeFormat::new().rate_suffix();
+
+    let mut buffer = [0; 32];
+    assert_eq!(format.write_to(tc, &mut buffer), Ok("23:59:59;59@59.94DF"));
+
+    let mut buffer = [0; 18];
+    assert!(format.write_to(tc, &mut buffer).is_err());
+}
+
+// A frame rate defined outside of the crate, with no AnyFrameRate.
+struct FrameRate15;
+
+impl FrameRate for FrameRate15 {
+    const FPS: u32 = 15;
+    const DROP_FRAME: bool = false;
+    const MAX_FRAMES: u32 = 86400 * 15;
+}
+
+#[test]
+fn timecode_format_custom_frame_rate() {
+    let tc = Timecode::<FrameRate15>::new(1, 2, 3, 14).unwrap();
+
+    assert_eq!(tc.to_string(), "01:02:03:14");
+    assert_eq!(format!("{:>12}", tc), " 01:02:03:14");
+    assert_eq!(format!("{:#}", tc), "01:02:03:14@15");
+
+    let format = TimecodeFormat::new().seconds(1).rate_suffix();
+    assert_eq!(format.display(tc).to_string(), "3723.9@15");
+}
+
+// Test lenient parser
+
+#[test]
+fn timecode_parser_syntaxes() {
+    let parser = TimecodeParser::new();
+
+    let cases = [
+        ("01:00:00:00", Syntax::Standard, "01:00:00:00"),
+        (" 1:0:0:0 ", Syntax::Unpadded, "01:00:00:00"),
+        ("1:2:3:4", Syntax::Unpadded, "01:02:03:04"),
+        ("01020304", Syntax::Packed, "01:02:03:04"),
+        ("+1234", Syntax::FrameCount, "00:00:49:09"),
+        ("1234", Syntax::FrameCount, "00:00:49:09"),
+        ("10s", Syntax::Units, "00:00:10:00"),
+        ("1h2m", Syntax::Units, "01:02:00:00"),
+        ("1m30.5s12f", Syntax::Units, "00:01:31:00"),
+        ("7f", Syntax::Units, "00:00:00:07"),
+    ];
+
+    for &(input, syntax, expected) in cases.iter() {
+        let parsed = parser.parse::<FrameRate25>(input).unwrap();
+        assert_eq!(parsed.syntax, syntax, "{}", input);
+        assert_eq!(parsed.timecode.to_string(), expected, "{}", input);
+        assert_eq!(parsed.frame_rate, None);
+    }
+
+    let parsed = parser.parse::<FrameRate2997>("10s").unwrap();
+    assert_eq!(parsed.timecode.to_string(), "00:00:10;00");
+
+    let parsed = parser.parse::<FrameRate2997>("1:00:00;00").unwrap();
+    assert_eq!(parsed.timecode.to_string(), "01:00:00;00");
+
+    let parsed = parser.parse::<FrameRate2997>("1.2.3.4").unwrap();
+    assert_eq!(parsed.syntax, Syntax::Unpadded);
+    assert_eq!(parsed.timecode.to_string(), "01:02:03;04");
+
+    let parsed = parser.parse::<FrameRate120>("000001000").unwrap();
+    assert_eq!(parsed.timecode.to_string(), "00:00:01:000");
+}
+
+#[test]
+fn timecode_parser_errors() {
+    let parser = TimecodeParser::new();
+
+    let inputs = [
+        "",
+        "1h1h",
+        "1m2h",
+        "10x",
+        "1:2:3",
+        "1:2:3:4:5",
+        "1.5h",
+        "1.2:3;4",
+        "1;2;3:4",
+        "1:2;3;4",
+    ];
+
+    for input in inputs.iter() {
+        match parser.parse::<FrameRate25>(input) {
+            Err(TimecodeError {
+                kind: TimecodeErrorKind::InvalidFormat,
+                ..
+            }) => {}
+            other => panic!("{}: {:?}", input, other),
+        }
+    }
+
+    let out_of_range = [
+        ("1:60:0:0", TimecodeField::Minute, 60, 59, 2),
+        ("01000025", TimecodeField::Frame, 25, 24, 6),
+        (" 01000025", TimecodeField::Frame, 25, 24, 7),
+        ("+2160000", TimecodeField::FrameNumber, 2160000, 2159999, 1),
+        ("2160000", TimecodeField::FrameNumber, 2160000, 2159999, 0),
+        ("24h", TimecodeField::FrameNumber, 2160000, 2159999, 0),
+    ];
+
+    for &(input, field, value, max, position) in out_of_range.iter() {
+        let error = parser.parse::<FrameRate25>(input).unwrap_err();
+        assert_eq!(error.kind, TimecodeErrorKind::InvalidTimecode, "{}", input);
+        assert_eq!(
+            error.reason,
+            Some(TimecodeErrorReason::OutOfRange {
+                field,
+                value,
+                min: 0,
+                max,
+            }),
+            "{}",
+            input
+        );
+        assert_eq!(error.position, Some(position), "{}", input);
+    }
+
+    let error = parser.parse::<FrameRate2997>("0:1:0;0").unwrap_err();
+    assert_eq!(
+        error.reason,
+        Some(TimecodeErrorReason::DroppedFrame {
+            minute: 1,
+            frame: 0,
+        })
+    );
+    assert_eq!(error.position, Some(6));
+}
+
+#[test]
+fn timecode_parser_drop_frame_notation() {
+    let parser = TimecodeParser::new();
+
+    for &(input, position) in [("00:00:10;00", 8), (" 0:0:10.0", 7)].iter() {
+        let error = parser.parse::<FrameRate24>(input).unwrap_err();
+        assert_eq!(error.kind, TimecodeErrorKind::InvalidFormat, "{}", input);
+        assert_eq!(
+            error.reason,
+            Some(TimecodeErrorReason::DropFrameNotation),
+            "{}",
+            input
+        );
+        assert_eq!(error.position, Some(position), "{}", input);
+    }
+
+    assert!(parser.parse::<FrameRate25>("1.2.3.4").is_err());
+    assert!(parser.parse::<FrameRate2997>("1.2:3;4").is_err());
+}
+
+#[test]
+fn timecode_parser_rate_suffix() {
+    let parser = TimecodeParser::new();
+
+    let parsed = parser.parse::<FrameRate25>("00:00:10:00@25").unwrap();
+    assert_eq!(parsed.frame_rate, Some(AnyFrameRate::Fps25));
+    assert_eq!(parsed.timecode.to_string(), "00:00:10:00");
+
+    let parsed = parser.parse::<FrameRate2997>("10s @ 29.97").unwrap();
+    assert_eq!(parsed.frame_rate, Some(AnyFrameRate::Fps2997));
+
+    let parsed = parser.parse::<FrameRate2997Ndf>("1:0:0:0@29.97").unwrap();
+    assert_eq!(parsed.frame_rate, Some(AnyFrameRate::Fps2997Ndf));
+
+    for input in ["00:00:10:00@24", "00:00:10;00@29.97NDF"].iter() {
+        match parser.parse::<FrameRate2997>(input) {
+            Err(TimecodeError {
+                kind: TimecodeErrorKind::FrameRateMismatch,
+                ..
+            }) => {}
+            other => panic!("{}: {:?}", input, other),
+        }
+    }
+
+    let parser = parser.rate_suffix(false);
+    assert!(parser.parse::<FrameRate25>("00:00:10:00@25").is_err());
+}
+
+#[test]
+fn timecode_parser_configuration() {
+    let parser = TimecodeParser::strict();
+    assert!(parser.accepts(Syntax::Standard));
+    assert!(!parser.accepts(Syntax::Units));
+
+    for input in ["01:00:00:00", "1:00:00:00", "01000000", "10s"].iter() {
+        assert_eq!(
+            parser.parse::<FrameRate25>(input).ok().map(|p| p.timecode),
+            input.parse::<Timecode<FrameRate25>>().ok()
+        );
+    }
+
+    let parser = parser.accept(Syntax::Units);
+    let parsed = parser.parse::<FrameRate25>("10s").unwrap();
+    assert_eq!(parsed.syntax, Syntax::Units);
+
+    let parser = TimecodeParser::default().reject(Syntax::Packed);
+    let parsed = parser.parse::<FrameRate25>("01000000").unwrap();
+    assert_eq!(parsed.syntax, Syntax::FrameCount);
+    assert_eq!(parsed.timecode.to_string(), "11:06:40:00");
+}
+
+// Test error reasons and positions
+
+#[test]
+fn timecode_error_reasons() {
+    use video_timecode::TimecodeErrorReason::*;
+
+    let cases = [
+        (
+            "24:00:00:00",
+            TimecodeErrorKind::InvalidTimecode,
+            OutOfRange {
+                field: TimecodeField::Hour,
+                value: 24,
+                min: 0,
+                max: 23,
+            },
+            0,
+        ),
+        (
+            "00:60:00:00",
+            TimecodeErrorKind::InvalidFormat,
+            OutOfRange {
+                field: TimecodeField::Minute,
+                value: 60,
+                min: 0,
+                max: 59,
+            },
+            3,
+        ),
+        (
+            "00:00:61:00",
+            TimecodeErrorKind::InvalidFormat,
+            OutOfRange {
+                field: TimecodeField::Second,
+                value: 61,
+                min: 0,
+                max: 59,
+            },
+            6,
+        ),
+        (
+            "00:00:00:30",
+            TimecodeErrorKind::InvalidTimecode,
+            OutOfRange {
+                field: TimecodeField::Frame,
+                value: 30,
+                min: 0,
+                max: 29,
+            },
+            9,
+        ),
+        (
+            "00:02:00;01",
+            TimecodeErrorKind::InvalidTimecode,
+            DroppedFrame {
+                minute: 2,
+                frame: 1,
+            },
+            9,
+        ),
+        (
+            "00:00x00:00",
+            TimecodeErrorKind::InvalidFormat,
+            UnexpectedCharacter {
+                found: 'x',
+                expected: "':'",
+            },
+            5,
+        ),
+        (
+            "0:00:00:00",
+            TimecodeErrorKind::InvalidFormat,
+            UnexpectedCharacter {
+                found: ':',
+                expected: "a digit",
+            },
+            1,
+        ),
+        (
+            "00:00:00",
+            TimecodeErrorKind::InvalidFormat,
+            UnexpectedEnd {
+                expected: "a separator",
+            },
+            8,
+        ),
+        (
+            "00:00:00:000",
+            TimecodeErrorKind::InvalidFormat,
+            UnexpectedCharacter {
+                found: '0',
+                expected: "the end",
+            },
+            11,
+        ),
+    ];
+
+    for &(input, ref kind, ref reason, position) in cases.iter() {
+        let error = input.parse::<Timecode<FrameRate2997>>().unwrap_err();
+        assert_eq!(&error.kind, kind, "{}", input);
+        assert_eq!(error.reason.as_ref(), Some(reason), "{}", input);
+        assert_eq!(error.position, Some(position), "{}", input);
+    }
+
+    let error = "00:00:00;00".parse::<Timecode<FrameRate25>>().unwrap_err();
+    assert_eq!(error.reason, Some(DropFrameNotation));
+    assert_eq!(error.position, Some(8));
+
+    let error = Timecode::<FrameRate25>::new(0, 0, 0, 25).unwrap_err();
+    assert_eq!(error.position, None);
+    assert_eq!(
+        error.to_string(),
+        "invalid timecode: frame 25 is not between 0 and 24"
+    );
+}
+
+#[test]
+fn timecode_error_positions_with_prefixes() {
+    let error = "2d 25:00:00:00"
+        .parse::<ExtendedTimecode<FrameRate25>>()
+        .unwrap_err();
+    assert_eq!(error.position, Some(3));
+
+    let error = "-00:00:10:25"
+        .parse::<TimecodeDuration<FrameRate25>>()
+        .unwrap_err();
+    assert_eq!(error.position, Some(10));
+
+    let error = SubframeTimecode::<FrameRate25>::parse(80, "00:00:10:00.80")
+        .unwrap_err();
+    assert_eq!(
+        error.reason,
+        Some(TimecodeErrorReason::OutOfRange {
+            field: TimecodeField::Subframe,
+            value: 80,
+            min: 0,
+            max: 79,
+        })
+    );
+    assert_eq!(error.position, Some(12));
+
+    let error = "00:00:00:00@23".parse::<AnyTimecode>().unwrap_err();
+    assert_eq!(error.position, Some(12));
+
+    let parser = TimecodeParser::new();
+    let error = parser.parse::<FrameRate25>("  00:00:10:25").unwrap_err();
+    assert_eq!(error.position, Some(11));
+}
+
+#[test]
+fn timecode_error_display() {
+    let error = TimecodeError::from(TimecodeErrorKind::FrameRateMismatch);
+    assert_eq!(error.to_string(), "frame rates do not match");
+
+    let error = "00:0".parse::<Timecode<FrameRate24>>().unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid timecode format: expected a digit, found the end, at \
+         position 4"
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn timecode_error_is_std_error() {
+    let error = "00:0".parse::<Timecode<FrameRate24>>().unwrap_err();
+    let error: Box<dyn std::error::Error> = Box::new(error);
+    assert!(error.to_string().starts_with("invalid timecode format"));
+}
+
+// Test formatting without allocating
+
+#[test]
+fn timecode_format_into_fmt_write() {
+    use std::fmt::{self, Write};
+
+    struct Buffer {
+        bytes: [u8; 32],
+        length: usize,
+    }
+
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.length + s.len();
+            self.bytes
+                .get_mut(self.length..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.length = end;
+            Ok(())
+        }
+    }
+
+    let tc = Timecode::<FrameRate2997>::new(1, 0, 0, 2).unwrap();
+    let mut buffer = Buffer {
+        bytes: [0; 32],
+        length: 0,
+    };
+
+    TimecodeFormat::new().write(&mut buffer, tc).unwrap();
+    write!(buffer, " {}", tc.frame_number).unwrap();
+    assert_eq!(&buffer.bytes[..buffer.length], b"01:00:00;02 107894");
+
+    let format = TimecodeFormat::new().rate_suffix();
+    assert!(format.write(&mut buffer, tc).is_err());
+
+    let tc = Timecode::<FrameRate25>::from_seconds(0.02, Rounding::Floor);
+    assert_eq!(tc.frame_number, 0);
+    let tc = Timecode::<FrameRate25>::from_seconds(86399.96, Rounding::Floor);
+    assert_eq!(tc.to_string(), "23:59:59:24");
+}
+
+// Test LTC frames
+
+#[test]
+fn ltc_encode() {
+    use video_timecode::ltc::*;
+
+    let tc = Timecode::<FrameRate30>::new(0, 0, 0, 0).unwrap();
+    assert_eq!(
+        LtcFrame::new(tc).encode(),
+        [0, 0, 0, 0x08, 0, 0, 0, 0, 0xfc, 0xbf]
+    );
+
+    let tc = Timecode::<FrameRate25>::new(0, 0, 0, 0).unwrap();
+    assert_eq!(
+        LtcFrame::new(tc).encode(),
+        [0, 0, 0, 0, 0, 0, 0, 0x08, 0xfc, 0xbf]
+    );
+
+    let tc = Timecode::<FrameRate30>::new(12, 34, 56, 17).unwrap();
+    assert_eq!(
+        LtcFrame::new(tc).encode(),
+        [0x07, 0x01, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01, 0xfc, 0xbf]
+    );
+
+    let tc = Timecode::<FrameRate2997>::new(0, 0, 0, 0).unwrap();
+    let mut frame = LtcFrame::new(tc);
+    frame.user_bits = 0x8765_4321;
+    frame.color_frame = true;
+    frame.binary_group_flags = 0b111;
+    assert_eq!(
+        frame.encode(),
+        [0x10, 0x2c, 0x30, 0x48, 0x50, 0x68, 0x70, 0x8c, 0xfc, 0xbf]
+    );
+}
+
+#[test]
+fn ltc_polarity_correction() {
+    use video_timecode::ltc::*;
+
+    let start = Timecode::<FrameRate25>::new(0, 59, 0, 0).unwrap();
+
+    for tc in start.frames().take(3000) {
+        let mut frame = LtcFrame::new(tc);
+        frame.user_bits = tc.frame_number.wrapping_mul(2_654_435_761);
+
+        let zeros: u32 =
+            frame.encode().iter().map(|byte| byte.count_zeros()).sum();
+        assert_eq!(zeros % 2, 0, "{}", tc);
+        assert_eq!(LtcFrame::decode(&frame.encode()).unwrap(), frame);
+    }
+}
+
+#[test]
+fn ltc_decode() {
+    use video_timecode::ltc::*;
+
+    let tc = Timecode::<FrameRate2997>::new(1, 2, 3, 4).unwrap();
+    let mut frame = LtcFrame::new(tc);
+    frame.user_bits = 0xdead_beef;
+    frame.binary_group_flags = 0b101;
+    let bytes = frame.encode();
+
+    let decoded = LtcFrame::<FrameRate2997>::decode(&bytes).unwrap();
+    assert_eq!(decoded.timecode.to_string(), "01:02:03;04");
+    assert_eq!(decoded.user_bits, 0xdead_beef);
+    assert!(!decoded.color_frame);
+    assert_eq!(decoded.binary_group_flags, 0b101);
+
+    match LtcFrame::<FrameRate30>::decode(&bytes) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::FrameRateMismatch,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+
+    let mut bad_sync = bytes;
+    bad_sync[9] = 0xfd;
+    match LtcFrame::<FrameRate2997>::decode(&bad_sync) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+
+    let mut bad_digit = bytes;
+    bad_digit[2] = (bad_digit[2] & 0xf0) | 0x0a;
+    match LtcFrame::<FrameRate2997>::decode(&bad_digit) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+
+    // Frame 35.
+    let bytes = [0x05, 0x03, 0, 0, 0, 0, 0, 0, 0xfc, 0xbf];
+    match LtcFrame::<FrameRate30>::decode(&bytes) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::InvalidTimecode,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+}
+
+// Test LTC audio
+
+#[cfg(feature = "std")]
+fn decode_ltc<T>(samples: &[f32], sample_rate: u32) -> Vec<ltc::DecodedFrame<T>>
+where
+    T: ltc::LtcFrameRate,
+{
+    let mut decoder = ltc::LtcDecoder::new(sample_rate);
+    let mut decoded: Vec<_> = decoder.decode(samples).collect();
+    decoded.extend(decoder.finish());
+    decoded
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ltc_audio_wav_round_trip() {
+    use video_timecode::ltc::*;
+
+    let start = Timecode::<FrameRate2997>::new(0, 0, 59, 25).unwrap();
+    let frames: Vec<LtcFrame<FrameRate2997>> = start
+        .frames()
+        .take(40)
+        .map(|tc| LtcFrame {
+            user_bits: tc.frame_number,
+            ..LtcFrame::new(tc)
+        })
+        .collect();
+
+    for &sample_rate in &[44100, 48000, 96000] {
+        let samples = LtcGenerator::new(sample_rate)
+            .generate(frames.iter().cloned());
+
+        let mut file = Vec::new();
+        Wav::new(sample_rate, 1, samples).write(&mut file).unwrap();
+        let wav = Wav::read(&file[..]).unwrap();
+        assert_eq!(wav.sample_rate, sample_rate);
+
+        let samples: Vec<f32> = wav.channel(0).collect();
+        let decoded = decode_ltc::<FrameRate2997>(&samples, sample_rate);
+        assert_eq!(decoded.len(), frames.len());
+
+        let offset = start.to_sample(sample_rate) as i64;
+
+        for (decoded, frame) in decoded.iter().zip(frames.iter()) {
+            assert_eq!(decoded.frame, *frame);
+            assert_eq!(decoded.direction, Direction::Forward);
+
+            let expected = frame.timecode.to_sample(sample_rate) as i64;
+            assert!((decoded.sample as i64 - (expected - offset)).abs() <= 1);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ltc_audio_reverse() {
+    use video_timecode::ltc::*;
+
+    let start = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+    let mut samples =
+        LtcGenerator::new(48000).generate(start.frames().take(11));
+    samples.reverse();
+
+    // The audio starts in the middle of the last frame, after the
+    // transition at the start of the next one, so that frame is missed.
+
+    let decoded = decode_ltc::<FrameRate25>(&samples, 48000);
+    let timecodes: Vec<String> = decoded
+        .iter()
+        .map(|decoded| decoded.frame.timecode.to_string())
+        .collect();
+    assert_eq!(
+        timecodes,
+        [
+            "10:00:00:09",
+            "10:00:00:08",
+            "10:00:00:07",
+            "10:00:00:06",
+            "10:00:00:05",
+            "10:00:00:04",
+            "10:00:00:03",
+            "10:00:00:02",
+            "10:00:00:01",
+            "10:00:00:00",
+        ]
+    );
+
+    for (i, decoded) in decoded.iter().enumerate() {
+        assert_eq!(decoded.direction, Direction::Reverse);
+
+        // The last sample of the frame, reversed.
+        let expected = 1920 * (i as i64 + 2) - 1;
+        assert!((decoded.sample as i64 - expected).abs() <= 1);
+    }
+}
+
+/// Play samples at a speed that changes linearly from `from` to `to`, with
+/// linear interpolation.
+#[cfg(feature = "std")]
+fn varispeed(samples: &[f32], from: f64, to: f64) -> Vec<f32> {
+    let mut output = Vec::new();
+    let mut position = 0.0;
+
+    while position < (samples.len() - 1) as f64 {
+        let i = position as usize;
+        let fraction = (position - i as f64) as f32;
+        output.push(samples[i] * (1.0 - fraction) + samples[i + 1] * fraction);
+
+        position += from + (to - from) * position / samples.len() as f64;
+    }
+
+    output
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ltc_audio_varispeed() {
+    use video_timecode::ltc::*;
+
+    let start = Timecode::<FrameRate24>::new(1, 0, 0, 0).unwrap();
+    let samples = LtcGenerator::new(48000).generate(start.frames().take(48));
+
+    for &(from, to) in &[(0.8, 0.8), (1.2, 1.2), (0.9, 1.25), (1.25, 0.75)] {
+        let decoded =
+            decode_ltc::<FrameRate24>(&varispeed(&samples, from, to), 48000);
+
+        // The first frame can be missed while the decoder finds the speed.
+        assert!(decoded.len() >= 47, "{} to {}", from, to);
+
+        for (decoded, tc) in decoded.iter().rev().zip(
+            start.frames().take(48).collect::<Vec<_>>().into_iter().rev(),
+        ) {
+            assert_eq!(decoded.frame.timecode, tc);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ltc_audio_level_and_rise_time() {
+    use std::time::Duration;
+    use video_timecode::ltc::*;
+
+    let start = Timecode::<FrameRate30>::new(0, 0, 0, 0).unwrap();
+
+    for &rise_time in &[0, 25, 100, 1000] {
+        let samples = LtcGenerator::new(44100)
+            .level(0.1)
+            .rise_time(Duration::from_micros(rise_time))
+            .generate(start.frames().take(5));
+        assert_eq!(samples.len(), 7350);
+        assert!(samples.iter().all(|sample| sample.abs() <= 0.1));
+
+        let decoded = decode_ltc::<FrameRate30>(&samples, 44100);
+        assert_eq!(decoded.len(), 5, "{} µs", rise_time);
+    }
+
+    let samples = LtcGenerator::new(48000)
+        .rise_time(Duration::from_micros(0))
+        .generate(start.frames().take(1));
+    assert!(samples.iter().all(|sample| sample.abs() == 0.5));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ltc_audio_wav_formats() {
+    use video_timecode::ltc::*;
+
+    // Two channels of 24-bit samples, with a chunk before the format.
+    let mut file = Vec::new();
+    file.extend_from_slice(b"RIFF\x00\x00\x00\x00WAVE");
+    file.extend_from_slice(b"LIST\x03\x00\x00\x00abc\x00");
+    file.extend_from_slice(b"fmt \x10\x00\x00\x00\x01\x00\x02\x00");
+    file.extend_from_slice(&48000u32.to_le_bytes());
+    file.extend_from_slice(&288000u32.to_le_bytes());
+    file.extend_from_slice(b"\x06\x00\x18\x00data\x0c\x00\x00\x00");
+    file.extend_from_slice(&[0, 0, 0x40, 0, 0, 0xc0, 0, 0, 0, 0, 0, 0x80]);
+
+    let wav = Wav::read(&file[..]).unwrap();
+    assert_eq!(wav.sample_rate, 48000);
+    assert_eq!(wav.channels, 2);
+    assert_eq!(wav.channel(0).collect::<Vec<_>>(), [0.5, 0.0]);
+    assert_eq!(wav.channel(1).collect::<Vec<_>>(), [-0.5, -1.0]);
+
+    let error = Wav::read(&b"RIFF\x00\x00\x00\x00AVI "[..]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn ltc_audio_wav_header_overflow() {
+    use video_timecode::ltc::*;
+
+    for wav in [
+        Wav::new(48000, 40000, vec![0.0; 4]),
+        Wav::new(u32::MAX, 1, vec![0.0; 4]),
+    ]
+    .iter()
+    {
+        let mut file = Vec::new();
+        let error = wav.write(&mut file).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(file.is_empty());
+    }
+}
+
+// Test VITC
+
+#[test]
+fn vitc_encode() {
+    use video_timecode::vitc::*;
+
+    // Only the sync pairs are set, and the CRC has one bit to even them
+    // out.
+    let tc = Timecode::<FrameRate30>::new(0, 0, 0, 0).unwrap();
+    assert_eq!(
+        VitcFrame::new(tc).encode(),
+        [0x01, 0x04, 0x10, 0x40, 0, 0x01, 0x04, 0x10, 0x40, 0, 0x01, 0x01]
+    );
+
+    // The field mark is bit 27 of the data, or bit 59 at 25 fps, which are
+    // bits 35 and 75 of the word, after the sync pairs.
+    let tc = Timecode::<FrameRate30>::new(0, 0, 0, 0).unwrap();
+    let frame = VitcFrame {
+        field_mark: true,
+        ..VitcFrame::new(tc)
+    };
+    assert_eq!(frame.encode()[4], 0x08);
+
+    let tc = Timecode::<FrameRate25>::new(0, 0, 0, 0).unwrap();
+    let frame = VitcFrame {
+        field_mark: true,
+        ..VitcFrame::new(tc)
+    };
+    assert_eq!(frame.encode()[9], 0x08);
+
+    // The exclusive or of every eighth bit of a word is zero.
+    let tc = Timecode::<FrameRate2997>::new(12, 34, 56, 17).unwrap();
+    let frame = VitcFrame {
+        user_bits: 0xdead_beef,
+        color_frame: true,
+        binary_group_flags: 0b011,
+        ..VitcFrame::new(tc)
+    };
+    let bytes = frame.encode();
+    assert_eq!(bytes.iter().fold(0, |crc, byte| crc ^ byte), 0);
+    assert_eq!(bytes[11] & 0xfc, 0);
+}
+
+#[test]
+fn vitc_decode() {
+    use video_timecode::vitc::*;
+
+    let tc = Timecode::<FrameRate2997>::new(12, 34, 56, 17).unwrap();
+    let frame = VitcFrame {
+        user_bits: 0xdead_beef,
+        color_frame: true,
+        binary_group_flags: 0b101,
+        field_mark: true,
+        ..VitcFrame::new(tc)
+    };
+    let bytes = frame.encode();
+    assert_eq!(VitcFrame::<FrameRate2997>::decode(&bytes).unwrap(), frame);
+
+    match VitcFrame::<FrameRate2997Ndf>::decode(&bytes) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::FrameRateMismatch,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+
+    // Any changed bit breaks the CRC, or a sync pair.
+    for bit in 0..90 {
+        let mut changed = bytes;
+        changed[bit / 8] ^= 1 << (bit % 8);
+        match VitcFrame::<FrameRate2997>::decode(&changed) {
+            Err(TimecodeError {
+                kind: TimecodeErrorKind::InvalidFormat,
+                ..
+            }) => {}
+            other => panic!("bit {}: {:?}", bit, other),
+        }
+    }
+
+    // The unused bits are ignored.
+    let mut unused = bytes;
+    unused[11] |= 0xfc;
+    assert_eq!(VitcFrame::<FrameRate2997>::decode(&unused).unwrap(), frame);
+}
+
+#[test]
+fn vitc_line() {
+    use std::time::Duration;
+    use video_timecode::vitc::*;
+
+    let start = Timecode::<FrameRate25>::new(10, 0, 0, 0).unwrap();
+
+    for (i, tc) in start.frames().take(50).enumerate() {
+        let frame = VitcFrame {
+            user_bits: tc.frame_number,
+            field_mark: i % 2 == 1,
+            ..VitcFrame::new(tc)
+        };
+
+        for &sample_rate in &[13_500_000, 27_000_000] {
+            let line_format = VitcLine::new(sample_rate);
+            let mut line = vec![0; sample_rate as usize / 15625];
+            line_format.render(&frame, &mut line);
+
+            assert_eq!(line_format.read::<FrameRate25>(&line).unwrap(), frame);
+        }
+    }
+
+    let tc = Timecode::<FrameRate2997>::new(1, 0, 0, 0).unwrap();
+    let frame = VitcFrame::new(tc);
+    let line_format = VitcLine::new(13_500_000);
+
+    // Sharp edges, and levels in the whole range.
+    let mut line = [0; 858];
+    line_format
+        .clone()
+        .levels(0, 255)
+        .rise_time(Duration::from_nanos(0))
+        .render(&frame, &mut line);
+    assert!(line.iter().all(|&sample| sample == 0 || sample == 255));
+    assert_eq!(line_format.read::<FrameRate2997>(&line).unwrap(), frame);
+
+    // A capture of a line from tape, where the word is in a different place
+    // and a bit slower than it should be, at a lower level.
+    let mut line = [0; 858];
+    VitcLine::new(13_700_000)
+        .start(Duration::from_micros(5))
+        .levels(30, 120)
+        .render(&frame, &mut line);
+    assert_eq!(line_format.read::<FrameRate2997>(&line).unwrap(), frame);
+
+    match line_format.read::<FrameRate2997>(&[16; 858]) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+
+    // Cut off at the end.
+    match line_format.read::<FrameRate2997>(&line[..300]) {
+        Err(TimecodeError {
+            kind: TimecodeErrorKind::InvalidFormat,
+            ..
+        }) => {}
+        other => panic!("{:?}", other),
+    }
+}